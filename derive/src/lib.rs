@@ -0,0 +1,381 @@
+/// Derive macro for declarative puzzle input parsing.
+///
+/// `#[derive(PuzzleParse)]` implements `advent_of_code::parse::PuzzleParse` and `FromStr` for
+/// structs and enums. See the documentation of [`macro@PuzzleParse`] for the supported attributes.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Derives `PuzzleParse` and `FromStr`.
+///
+/// # Structs
+/// - `#[parse(pattern = "Game {id}: {rounds}")]` on the struct matches the input against literal
+///   text with one `{field}` capture per field (`{0}`, `{1}`, ... for tuple structs). Use `{{` and
+///   `}}` for literal braces. A struct with a single field may omit the pattern, its field then
+///   receives the whole input.
+/// - `#[parse(sep = ";")]` on a collection field (e.g. `Vec<T>`) splits its capture on `sep` and
+///   parses every non-empty, trimmed part as `T`.
+/// - `#[parse(lines)]` on a collection field does the same for every non-blank line.
+///
+/// # Enums
+/// Only unit variants are supported. Each variant matches `#[parse(pattern = "...")]` exactly, or
+/// its lowercased name if no pattern is given.
+#[proc_macro_derive(PuzzleParse, attributes(parse))]
+pub fn derive_puzzle_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => expand_struct(input, &data.fields)?,
+        Data::Enum(data) => expand_enum(data)?,
+        Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::advent_of_code::parse::PuzzleParse for #name #ty_generics #where_clause {
+            fn parse_span(
+                span: ::advent_of_code::parse::Span<'_>,
+            ) -> ::advent_of_code::parse::Result<Self> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::core::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::advent_of_code::parse::ParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as ::advent_of_code::parse::PuzzleParse>::parse_span(
+                    ::advent_of_code::parse::Span::new(s),
+                )
+            }
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Default)]
+struct Attributes {
+    pattern: Option<LitStr>,
+    sep: Option<LitStr>,
+    lines: bool,
+}
+
+fn parse_attributes(attrs: &[syn::Attribute]) -> syn::Result<Attributes> {
+    let mut res = Attributes::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("parse")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("pattern") {
+                res.pattern = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("sep") {
+                res.sep = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("lines") {
+                res.lines = true;
+            } else {
+                return Err(meta.error("expected `pattern`, `sep` or `lines`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if res.sep.is_some() && res.lines {
+        return Err(Error::new(
+            attrs[0].span(),
+            "`sep` and `lines` cannot be combined",
+        ));
+    }
+
+    Ok(res)
+}
+
+/// A pattern like `Game {id}: {rounds}`, split into the literals around each capture.
+struct Pattern {
+    literals: Vec<String>,
+    names: Vec<String>,
+}
+
+fn parse_pattern(lit: &LitStr) -> syn::Result<Pattern> {
+    let value = lit.value();
+    let mut chars = value.chars().peekable();
+    let mut literals = vec![String::new()];
+    let mut names = vec![];
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literals.last_mut().unwrap().push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literals.last_mut().unwrap().push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(Error::new_spanned(
+                                lit,
+                                format!("unterminated capture `{{{name}` in pattern"),
+                            ))
+                        }
+                    }
+                }
+                if name.is_empty() {
+                    return Err(Error::new_spanned(lit, "empty capture `{}` in pattern"));
+                }
+                if !names.is_empty() && literals.last().is_some_and(String::is_empty) {
+                    return Err(Error::new_spanned(
+                        lit,
+                        format!("capture `{{{name}}}` must be separated from the previous one"),
+                    ));
+                }
+                names.push(name);
+                literals.push(String::new());
+            }
+            '}' => return Err(Error::new_spanned(lit, "unmatched `}` in pattern")),
+            c => literals.last_mut().unwrap().push(c),
+        }
+    }
+
+    Ok(Pattern { literals, names })
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn expand_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream2> {
+    let attrs = parse_attributes(&input.attrs)?;
+    if attrs.sep.is_some() || attrs.lines {
+        return Err(Error::new_spanned(
+            input,
+            "`sep` and `lines` are field attributes",
+        ));
+    }
+
+    let field_names: Vec<String> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| f.ident.as_ref().map_or(i.to_string(), ToString::to_string))
+        .collect();
+
+    // the span each field is parsed from, as an expression.
+    let (matcher, captures): (TokenStream2, Vec<TokenStream2>) =
+        match &attrs.pattern {
+            Some(lit) => {
+                let pattern = parse_pattern(lit)?;
+                for name in &pattern.names {
+                    if !field_names.contains(name) {
+                        return Err(Error::new_spanned(
+                            lit,
+                            format!("pattern captures unknown field `{name}`"),
+                        ));
+                    }
+                }
+                let mut captures = vec![];
+                for name in &field_names {
+                    let Some(index) = pattern.names.iter().position(|n| n == name) else {
+                        return Err(Error::new_spanned(
+                            lit,
+                            format!("pattern does not capture field `{name}`"),
+                        ));
+                    };
+                    if pattern.names.iter().filter(|n| *n == name).count() > 1 {
+                        return Err(Error::new_spanned(
+                            lit,
+                            format!("pattern captures field `{name}` more than once"),
+                        ));
+                    }
+                    captures.push(quote!(__captures[#index]));
+                }
+                let literals = &pattern.literals;
+                (
+                    quote!(let __captures = span.match_pattern(&[#(#literals),*])?;),
+                    captures,
+                )
+            }
+            None if fields.len() == 1 => (quote!(), vec![quote!(span.trim())]),
+            None if fields.is_empty() => return Err(Error::new_spanned(
+                input,
+                "structs without fields need `#[parse(pattern = \"...\")]` to match their input",
+            )),
+            None => {
+                return Err(Error::new_spanned(
+                    input,
+                    "structs with more than one field need `#[parse(pattern = \"...\")]`",
+                ))
+            }
+        };
+
+    let values = fields
+        .iter()
+        .zip(&captures)
+        .map(|(field, capture)| expand_field(field, capture))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let construct = match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!(Self { #(#idents: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(Self(#(#values),*)),
+        Fields::Unit => quote!(Self),
+    };
+
+    Ok(quote! {
+        #matcher
+        ::core::result::Result::Ok(#construct)
+    })
+}
+
+fn expand_field(field: &syn::Field, capture: &TokenStream2) -> syn::Result<TokenStream2> {
+    let attrs = parse_attributes(&field.attrs)?;
+    if attrs.pattern.is_some() {
+        return Err(Error::new_spanned(
+            field,
+            "`pattern` is a struct or variant attribute",
+        ));
+    }
+
+    let ty = &field.ty;
+    let parts = if let Some(sep) = &attrs.sep {
+        quote!(#capture.split_str(#sep))
+    } else if attrs.lines {
+        quote!(#capture.lines())
+    } else {
+        return Ok(quote! {
+            <#ty as ::advent_of_code::parse::PuzzleParse>::parse_span(#capture)?
+        });
+    };
+
+    let element = collection_element(ty).ok_or_else(|| {
+        Error::new_spanned(
+            ty,
+            "`sep` and `lines` need a collection type such as `Vec<T>`",
+        )
+    })?;
+
+    Ok(quote! {
+        #parts
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .map(<#element as ::advent_of_code::parse::PuzzleParse>::parse_span)
+            .collect::<::advent_of_code::parse::Result<#ty>>()?
+    })
+}
+
+/// Returns `T` for a type like `Vec<T>`.
+fn collection_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn expand_enum(data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let mut arms = vec![];
+    let mut expected = vec![];
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "only unit variants are supported",
+            ));
+        }
+
+        let attrs = parse_attributes(&variant.attrs)?;
+        let text = attrs
+            .pattern
+            .map_or_else(|| variant.ident.to_string().to_lowercase(), |p| p.value());
+        let ident = &variant.ident;
+        arms.push(quote!(#text => ::core::result::Result::Ok(Self::#ident),));
+        expected.push(format!("`{text}`"));
+    }
+
+    let message = format!("expected one of {}, found", expected.join(", "));
+
+    Ok(quote! {
+        match span.as_str().trim() {
+            #(#arms)*
+            other => ::core::result::Result::Err(
+                span.trim().error(format!("{} `{}`", #message, other)),
+            ),
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, parse_pattern};
+    use proc_macro2::Span;
+    use syn::{DeriveInput, LitStr};
+
+    fn pattern_error(pattern: &str) -> String {
+        let lit = LitStr::new(pattern, Span::call_site());
+        parse_pattern(&lit).err().unwrap().to_string()
+    }
+
+    fn expand_error(input: &str) -> String {
+        let input: DeriveInput = syn::parse_str(input).unwrap();
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_patterns() {
+        let lit = LitStr::new("Game {id}: {{{rounds}}}", Span::call_site());
+        let pattern = parse_pattern(&lit).unwrap();
+        assert_eq!(pattern.names, ["id", "rounds"]);
+        assert_eq!(pattern.literals, ["Game ", ": {", "}"]);
+    }
+
+    #[test]
+    fn rejects_malformed_patterns() {
+        assert_eq!(
+            pattern_error("Game {id: {rounds"),
+            "unterminated capture `{id: {rounds` in pattern"
+        );
+        assert_eq!(
+            pattern_error("Game {"),
+            "unterminated capture `{` in pattern"
+        );
+        assert_eq!(pattern_error("{}"), "empty capture `{}` in pattern");
+        assert_eq!(pattern_error("a}"), "unmatched `}` in pattern");
+    }
+
+    #[test]
+    fn explains_missing_patterns() {
+        assert_eq!(
+            expand_error("struct Empty;"),
+            "structs without fields need `#[parse(pattern = \"...\")]` to match their input"
+        );
+        assert_eq!(
+            expand_error("struct Pair(u32, u32);"),
+            "structs with more than one field need `#[parse(pattern = \"...\")]`"
+        );
+    }
+}
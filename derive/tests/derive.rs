@@ -0,0 +1,154 @@
+use advent_of_code::parse::{ParseError, PuzzleParse};
+
+/* ------------------------------- day 02 ---------------------------------- */
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+#[parse(pattern = "Game {id}: {rounds}")]
+struct Game {
+    id: u32,
+    #[parse(sep = ";")]
+    rounds: Vec<Round>,
+}
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+struct Round {
+    #[parse(sep = ",")]
+    cubes: Vec<Cubes>,
+}
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+#[parse(pattern = "{count} {color}")]
+struct Cubes {
+    count: u32,
+    color: Color,
+}
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+    #[parse(pattern = "blue")]
+    Blue,
+}
+
+#[test]
+fn parses_day_02_game() {
+    let game: Game = "Game 12: 3 blue, 4 red; 1 red, 2 green".parse().unwrap();
+    assert_eq!(
+        game,
+        Game {
+            id: 12,
+            rounds: vec![
+                Round {
+                    cubes: vec![
+                        Cubes {
+                            count: 3,
+                            color: Color::Blue
+                        },
+                        Cubes {
+                            count: 4,
+                            color: Color::Red
+                        },
+                    ]
+                },
+                Round {
+                    cubes: vec![
+                        Cubes {
+                            count: 1,
+                            color: Color::Red
+                        },
+                        Cubes {
+                            count: 2,
+                            color: Color::Green
+                        },
+                    ]
+                },
+            ]
+        }
+    );
+}
+
+#[test]
+fn reports_day_02_errors() {
+    let err = "Game 1: 3 blue, 4 purple".parse::<Game>().unwrap_err();
+    assert_eq!(
+        err,
+        ParseError {
+            line: 1,
+            column: 19,
+            message: "expected one of `red`, `green`, `blue`, found `purple`".into()
+        }
+    );
+
+    let err = "Game x: 3 blue".parse::<Game>().unwrap_err();
+    assert_eq!((err.line, err.column), (1, 6));
+    assert!(err.message.starts_with("invalid number `x`"));
+
+    let err = "Gme 1: 3 blue".parse::<Game>().unwrap_err();
+    assert_eq!(err.to_string(), "line 1, column 1: expected `Game `");
+}
+
+/* ------------------------------- day 05 ---------------------------------- */
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+#[parse(pattern = "seeds: {seeds}\n\n{layers}")]
+struct Almanac {
+    #[parse(sep = " ")]
+    seeds: Vec<u64>,
+    #[parse(sep = "\n\n")]
+    layers: Vec<MapLayer>,
+}
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+#[parse(pattern = "{name} map:\n{maps}")]
+struct MapLayer {
+    name: String,
+    #[parse(lines)]
+    maps: Vec<Map>,
+}
+
+#[derive(PuzzleParse, Debug, PartialEq)]
+#[parse(pattern = "{0} {1} {2}")]
+struct Map(u64, u64, u64);
+
+const ALMANAC: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+";
+
+#[test]
+fn parses_day_05_almanac() {
+    let almanac: Almanac = ALMANAC.parse().unwrap();
+    assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+    assert_eq!(
+        almanac.layers,
+        vec![
+            MapLayer {
+                name: "seed-to-soil".into(),
+                maps: vec![Map(50, 98, 2), Map(52, 50, 48)]
+            },
+            MapLayer {
+                name: "soil-to-fertilizer".into(),
+                maps: vec![Map(0, 15, 37)]
+            }
+        ]
+    );
+}
+
+#[test]
+fn reports_day_05_errors() {
+    let input = ALMANAC.replace("52 50 48", "52 50");
+    let err = input.parse::<Almanac>().unwrap_err();
+    assert_eq!((err.line, err.column), (5, 4));
+    assert_eq!(err.message, "expected ` `");
+
+    let input = ALMANAC.replace("0 15 37", "0 15 37 4");
+    let err = input.parse::<Almanac>().unwrap_err();
+    assert_eq!((err.line, err.column), (8, 6));
+    assert!(err.message.starts_with("invalid number `37 4`"));
+}
@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Runs the libFuzzer targets for a bounded time each, seeding their corpus with the examples.
+#
+# usage: fuzz/run.sh [seconds per target] [day...]
+# example: `fuzz/run.sh 60 05 08`
+#
+# Needs a nightly toolchain and cargo-fuzz (`cargo install cargo-fuzz`).
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+seconds="${1:-30}"
+shift || true
+
+days=("$@")
+if [ ${#days[@]} -eq 0 ]; then
+    for target in fuzz/fuzz_targets/day*.rs; do
+        target="${target##*/day}"
+        days+=("${target%.rs}")
+    done
+fi
+
+# build every target first, so a target that does not compile fails before any fuzzing starts.
+for day in "${days[@]}"; do
+    cargo +nightly fuzz build "day$day"
+done
+
+for day in "${days[@]}"; do
+    corpus="fuzz/corpus/day$day"
+    mkdir -p "$corpus"
+    cp data/examples/"$day"*.txt "$corpus"/ 2>/dev/null || true
+    cargo +nightly fuzz run "day$day" "$corpus" -- -max_total_time="$seconds"
+done
@@ -0,0 +1,406 @@
+/// Arbitrary-precision signed integers, for answers that do not fit into a fixed-width integer.
+///
+/// Only what answers need is supported: conversion from primitive integers and decimal strings,
+/// `+`, `-`, `*`, comparison, [`Sum`] and [`Product`], and decimal formatting. The magnitude is
+/// stored in little-endian limbs of base 10⁹, so formatting needs no division.
+use std::cmp::Ordering;
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+const BASE: u32 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    /// Whether the number is below zero. Zero is never negative.
+    negative: bool,
+    /// The magnitude without trailing zero limbs, so zero has no limbs.
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string had no digits.
+    Empty,
+    InvalidDigit(char),
+}
+
+impl StdError for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "cannot parse an integer without digits."),
+            ParseError::InvalidDigit(c) => write!(f, "invalid digit `{c}` in integer."),
+        }
+    }
+}
+
+impl BigInt {
+    #[must_use]
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            limbs: self.limbs.clone(),
+        }
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: u128) -> Self {
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            #[allow(clippy::cast_possible_truncation)]
+            limbs.push((magnitude % u128::from(BASE)) as u32);
+            magnitude /= u128::from(BASE);
+        }
+        Self::from_parts(negative, limbs)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> Self {
+                Self::from_magnitude(false, n as u128)
+            }
+        }
+    )*};
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(n: $t) -> Self {
+                Self::from_magnitude(n < 0, (n as i128).unsigned_abs())
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl FromStr for BigInt {
+    type Err = ParseError;
+
+    /// Parses a decimal integer with an optional sign, e.g. `-123`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseError::InvalidDigit(c));
+        }
+
+        let limbs = digits
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, digit| limb * 10 + u32::from(digit - b'0'))
+            })
+            .collect();
+        Ok(Self::from_parts(negative, limbs))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = self.limbs.last().map_or("0".into(), u32::to_string);
+        for limb in self.limbs.iter().rev().skip(1) {
+            digits.push_str(&format!("{limb:09}"));
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let sum = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+        limbs.push(sum % BASE);
+        carry = sum / BASE;
+    }
+    limbs.push(carry);
+    limbs
+}
+
+/// `a - b` for `a >= b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &limb) in a.iter().enumerate() {
+        let subtrahend = b.get(i).unwrap_or(&0) + borrow;
+        if limb >= subtrahend {
+            limbs.push(limb - subtrahend);
+            borrow = 0;
+        } else {
+            limbs.push(limb + BASE - subtrahend);
+            borrow = 1;
+        }
+    }
+    limbs
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = vec![0_u64; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let product = limbs[i + j] + u64::from(x) * u64::from(y) + carry;
+            limbs[i + j] = product % u64::from(BASE);
+            carry = product / u64::from(BASE);
+        }
+        limbs[i + b.len()] += carry;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    limbs.into_iter().map(|limb| limb as u32).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        Self::from_parts(!self.negative, self.limbs)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &rhs.limbs));
+        }
+        match cmp_magnitude(&self.limbs, &rhs.limbs) {
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_magnitude(&rhs.limbs, &self.limbs))
+            }
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &rhs.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_magnitude(&self.limbs, &rhs.limbs),
+        )
+    }
+}
+
+/// Implements an operator for owned operands and its assigning version by delegating to the
+/// implementation for references.
+macro_rules! forward_op {
+    ($($op:ident::$method:ident, $assign:ident::$assign_method:ident;)*) => {$(
+        impl $op for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> BigInt {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &BigInt) -> BigInt {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $assign for BigInt {
+            fn $assign_method(&mut self, rhs: BigInt) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+
+        impl $assign<&BigInt> for BigInt {
+            fn $assign_method(&mut self, rhs: &BigInt) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    )*};
+}
+
+forward_op! {
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+}
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, n| acc + n)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::from(1), |acc, n| acc * n)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BigInt, ParseError};
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn formats_like_primitives() {
+        for n in [
+            0,
+            1,
+            -1,
+            999_999_999,
+            1_000_000_000,
+            -1_000_000_007,
+            i128::MIN,
+            i128::MAX,
+        ] {
+            assert_eq!(BigInt::from(n).to_string(), n.to_string());
+        }
+        assert_eq!(BigInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(format!("{:>6}", BigInt::from(-42)), "   -42");
+        assert_eq!(format!("{:+}", BigInt::from(42)), "+42");
+    }
+
+    #[test]
+    fn parses_decimal_strings() {
+        assert_eq!(big("+007"), BigInt::from(7));
+        assert_eq!(big("-0"), BigInt::zero());
+        assert!(!big("-0").is_negative());
+        assert_eq!(
+            big("-123456789012345678901234567890").to_string(),
+            "-123456789012345678901234567890"
+        );
+        assert_eq!("".parse::<BigInt>(), Err(ParseError::Empty));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseError::Empty));
+        assert_eq!("12a".parse::<BigInt>(), Err(ParseError::InvalidDigit('a')));
+        assert_eq!("1 2".parse::<BigInt>(), Err(ParseError::InvalidDigit(' ')));
+    }
+
+    #[test]
+    fn matches_primitive_arithmetic() {
+        let values: [i64; 9] = [
+            0,
+            1,
+            -1,
+            7,
+            -999_999_999,
+            1_000_000_000,
+            123_456_789_012,
+            -987_654_321_098,
+            i64::MAX,
+        ];
+        for a in values {
+            for b in values {
+                let (x, y) = (i128::from(a), i128::from(b));
+                let (big_a, big_b) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(&big_a + &big_b, BigInt::from(x + y), "{a} + {b}");
+                assert_eq!(&big_a - &big_b, BigInt::from(x - y), "{a} - {b}");
+                assert_eq!(&big_a * &big_b, BigInt::from(x * y), "{a} * {b}");
+                assert_eq!(big_a.cmp(&big_b), a.cmp(&b), "{a} <=> {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn grows_past_fixed_width() {
+        let factorial: BigInt = (1..=30_u32).map(BigInt::from).product();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+
+        let mut sum: BigInt = [u128::MAX, u128::MAX].into_iter().map(BigInt::from).sum();
+        assert_eq!(sum.to_string(), "680564733841876926926749214863536422910");
+        sum -= BigInt::from(u128::MAX);
+        sum *= BigInt::from(-1);
+        assert_eq!(sum, -BigInt::from(u128::MAX));
+        assert_eq!(sum.abs(), BigInt::from(u128::MAX));
+    }
+}
@@ -1,7 +1,11 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, validate = parse);
+
+#[path = "02/input.rs"]
+mod input;
+
+use advent_of_code::{trace, trace_scope};
+use input::{parse, ColorData, GameData};
 
-use lazy_static::lazy_static;
-use regex::Regex;
 pub fn part_one(input: &str) -> Option<u32> {
     let max = ColorData {
         red: 12,
@@ -9,11 +13,25 @@ pub fn part_one(input: &str) -> Option<u32> {
         blue: 14,
     };
 
-    let res: u32 = input
-        .split_terminator('\n')
-        .map(GameData::from)
+    let res: u32 = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         .filter(|c| {
-            c.colors.red <= max.red && c.colors.blue <= max.blue && c.colors.green <= max.green
+            let possible =
+                c.colors.red <= max.red && c.colors.blue <= max.blue && c.colors.green <= max.green;
+            if !possible {
+                trace_scope!("game {} is impossible", c.id);
+                for (color, count, limit) in [
+                    ("red", c.colors.red, max.red),
+                    ("green", c.colors.green, max.green),
+                    ("blue", c.colors.blue, max.blue),
+                ] {
+                    if count > limit {
+                        trace!("{count} {color} cubes shown, but only {limit} in the bag");
+                    }
+                }
+            }
+            possible
         })
         .map(|g| g.id)
         .sum();
@@ -22,9 +40,9 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let res: u32 = input
-        .split_terminator('\n')
-        .map(GameData::from)
+    let res: u32 = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         .map(to_power)
         .sum();
 
@@ -35,71 +53,15 @@ fn to_power(game: GameData) -> u32 {
     game.colors.red * game.colors.blue * game.colors.green
 }
 
-#[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
-struct ColorData {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
-
-#[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
-struct GameData {
-    id: u32,
-    colors: ColorData,
-}
-
-impl From<&str> for GameData {
-    fn from(value: &str) -> Self {
-        lazy_static! {
-            static ref GAME_RE: Regex = Regex::new(r"Game\s(\d+)").unwrap();
-        }
-        let id = GAME_RE.captures(value).unwrap()[1]
-            .parse::<u32>()
-            .unwrap_or(0);
-        GameData {
-            id,
-            colors: ColorData::from(value),
-        }
-    }
-}
-
-impl From<&str> for ColorData {
-    fn from(value: &str) -> Self {
-        lazy_static! {
-            static ref RED_RE: Regex = Regex::new(r"(\d+)\sred").unwrap();
-            static ref BLUE_RE: Regex = Regex::new(r"(\d+)\sblue").unwrap();
-            static ref GREEN_RE: Regex = Regex::new(r"(\d+)\sgreen").unwrap();
-        }
-
-        let red = RED_RE
-            .captures_iter(value)
-            .map(|c| c[1].parse::<u32>().unwrap())
-            .max()
-            .unwrap_or(0);
-
-        let blue = BLUE_RE
-            .captures_iter(value)
-            .map(|c| c[1].parse::<u32>().unwrap())
-            .max()
-            .unwrap_or(0);
-
-        let green = GREEN_RE
-            .captures_iter(value)
-            .map(|c| c[1].parse::<u32>().unwrap())
-            .max()
-            .unwrap_or(0);
-
-        ColorData { red, green, blue }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::parse::Span;
 
     #[test]
     fn test_parse_colors_from_line() {
-        let result = ColorData::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let result =
+            ColorData::parse(Span::new("3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")).unwrap();
         assert_eq!(
             ColorData {
                 red: 4,
@@ -112,7 +74,10 @@ mod tests {
 
     #[test]
     fn test_game_data_from_line() {
-        let result = GameData::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let result = GameData::parse(Span::new(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        ))
+        .unwrap();
         assert_eq!(
             GameData {
                 id: 1,
@@ -125,7 +90,7 @@ mod tests {
             result
         );
 
-        let result = GameData::from("Game 36: 1 blue, 9 red, 2 green; 11 red, 3 blue, 2 green; 2 green, 6 red; 8 green, 11 red, 3 blue; 4 green, 7 blue, 11 red; 9 green");
+        let result = GameData::parse(Span::new("Game 36: 1 blue, 9 red, 2 green; 11 red, 3 blue, 2 green; 2 green, 6 red; 8 green, 11 red, 3 blue; 4 green, 7 blue, 11 red; 9 green")).unwrap();
         assert_eq!(
             GameData {
                 id: 36,
@@ -138,6 +103,7 @@ mod tests {
             result
         )
     }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -149,4 +115,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2286));
     }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
@@ -0,0 +1,55 @@
+use advent_of_code::parse::{self, Span};
+
+/// Parses one [`GameData`] per line.
+pub fn parse(input: &str) -> parse::Result<Vec<GameData>> {
+    Span::new(input).lines().map(GameData::parse).collect()
+}
+
+#[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
+pub struct ColorData {
+    pub(super) red: u32,
+    pub(super) green: u32,
+    pub(super) blue: u32,
+}
+
+#[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
+pub struct GameData {
+    pub(super) id: u32,
+    pub(super) colors: ColorData,
+}
+
+impl GameData {
+    /// Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    pub(super) fn parse(line: Span) -> parse::Result<Self> {
+        let (id, rounds) = line.header("Game")?;
+        Ok(GameData {
+            id: id.number()?,
+            colors: ColorData::parse(rounds)?,
+        })
+    }
+}
+
+impl ColorData {
+    /// Parses the rounds of a game and keeps the maximum count seen for each color.
+    pub(super) fn parse(rounds: Span) -> parse::Result<Self> {
+        let mut colors = ColorData {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for cubes in rounds.split(';').flat_map(|round| round.split(',')) {
+            let (count, color) = cubes.trim().split_once(" ")?;
+            let count: u32 = count.number()?;
+            let max = match color.as_str() {
+                "red" => &mut colors.red,
+                "green" => &mut colors.green,
+                "blue" => &mut colors.blue,
+                _ => return Err(color.error(format!("unknown color `{color}`"))),
+            };
+            *max = (*max).max(count);
+        }
+
+        Ok(colors)
+    }
+}
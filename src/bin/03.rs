@@ -1,119 +1,142 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, validate = parse);
+
+#[path = "03/input.rs"]
+mod input;
+
 use std::collections::HashMap;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use advent_of_code::viz::{self, Color, Frame, Grid};
+use input::{parse, Number, Schematic};
 
 pub fn part_one(input: &str) -> Option<u32> {
-    lazy_static! {
-        static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
-        static ref SYM_RE: Regex = Regex::new(r"[^\d\.]").unwrap();
-    }
+    let schematic = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    let res = schematic
+        .numbers()
+        .filter(|(_, symbols)| !symbols.is_empty())
+        .map(|(n, _)| n.value)
+        .sum();
 
-    let line_len = input.find(char::is_whitespace).unwrap();
-    let input =
-        &(".".repeat(line_len) + &input.replace(char::is_whitespace, "") + &".".repeat(line_len));
-
-    let res: u32 = NUM_RE
-        .captures_iter(input)
-        .map(|c| {
-            let m = c.get(0).unwrap();
-            let start = m.start();
-            let end = m.end();
-
-            let left = if start % line_len != 0 {
-                start - 1
-            } else {
-                start
-            };
-
-            let right = if end % line_len < line_len - 1 {
-                end
-            } else {
-                end - 1
-            };
-
-            let valid = SYM_RE.is_match(
-                &[
-                    &input[left..left + 1],
-                    &input[right..right + 1],
-                    &input[left - line_len..right - line_len + 1],
-                    &input[left + line_len..right + line_len + 1],
-                ]
-                .concat(),
-            );
-            match valid {
-                true => c[0].parse::<u32>().expect("Couldn't convert to u32"),
-                false => 0u32,
+    for row in 1..schematic.rows.len() - 1 {
+        viz::emit(|| {
+            let mut sum = 0;
+            let mut highlights = vec![];
+            for (n, symbols) in schematic.numbers().filter(|(n, _)| n.y <= row) {
+                let color = if symbols.is_empty() {
+                    Color::Red
+                } else {
+                    sum += n.value;
+                    Color::Green
+                };
+                highlights.extend(n.cells().map(|cell| (cell, color)));
+                highlights.extend(symbols.into_iter().map(|cell| (cell, Color::Yellow)));
             }
-        })
-        .sum();
+            schematic
+                .frame(format!("Part 1: row {row}"), highlights)
+                .label("part number", Color::Green)
+                .label("no adjacent symbol", Color::Red)
+                .label("symbol", Color::Yellow)
+                .note(format!("sum: {sum}"))
+        });
+    }
 
     Some(res)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    lazy_static! {
-        static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
-        static ref GEAR_RE: Regex = Regex::new(r"\*").unwrap();
-    }
+    let schematic = parse(input).unwrap_or_else(|e| panic!("{e}"));
 
-    let mut map: HashMap<usize, Vec<u32>> = HashMap::new();
-    let line_len = input.find(char::is_whitespace).unwrap();
-    let input =
-        &(".".repeat(line_len) + &input.replace(char::is_whitespace, "") + &".".repeat(line_len));
-
-    NUM_RE.captures_iter(input).for_each(|c| {
-        let m = c.get(0).unwrap();
-        let start = m.start();
-        let end = m.end();
-
-        let left = if start % line_len != 0 {
-            start - 1
-        } else {
-            start
-        };
-
-        let right = if end % line_len < line_len - 1 {
-            end
-        } else {
-            end - 1
-        };
-
-        let val = c[0].parse().unwrap();
-        [
-            (&input[left..left + 1], left),
-            (&input[right..right + 1], right),
-            (
-                &input[left - line_len..right - line_len + 1],
-                left - line_len,
-            ),
-            (
-                &input[left + line_len..right + line_len + 1],
-                left + line_len,
-            ),
-        ]
-        .iter()
-        .for_each(|&s| {
-            GEAR_RE
-                .find_iter(s.0)
-                .for_each(|m| match map.get_mut(&(s.1 + m.start())) {
-                    Some(v) => v.push(val),
-                    None => {
-                        map.insert(s.1 + m.start(), vec![val]);
-                    }
-                });
-        })
-    });
-
-    let res: u32 = map
+    let res: u32 = schematic
+        .gears()
         .values()
         .filter(|&v| v.len() == 2)
-        .map(|v| v[0] * v[1])
+        .map(|v| v[0].value * v[1].value)
         .sum();
+
+    for row in 1..schematic.rows.len() - 1 {
+        viz::emit(|| {
+            let mut sum = 0;
+            let mut highlights = vec![];
+            for (&gear, numbers) in schematic.gears().iter().filter(|((y, _), _)| *y <= row) {
+                if numbers.len() == 2 {
+                    sum += numbers[0].value * numbers[1].value;
+                    highlights.push((gear, Color::Yellow));
+                    highlights.extend(
+                        numbers
+                            .iter()
+                            .flat_map(|n| n.cells())
+                            .map(|c| (c, Color::Cyan)),
+                    );
+                } else {
+                    highlights.push((gear, Color::Gray));
+                }
+            }
+            schematic
+                .frame(format!("Part 2: row {row}"), highlights)
+                .label("gear", Color::Yellow)
+                .label("gear part", Color::Cyan)
+                .label("not a gear", Color::Gray)
+                .note(format!("sum of gear ratios: {sum}"))
+        });
+    }
+
     Some(res)
 }
 
+impl Number {
+    /// The positions of the number's digits.
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.start..self.end).map(|x| (self.y, x))
+    }
+}
+
+impl Schematic {
+    fn at(&self, y: usize, x: usize) -> u8 {
+        self.rows[y][x]
+    }
+
+    /// Returns every number together with the positions of the symbols adjacent to it.
+    fn numbers(&self) -> impl Iterator<Item = (&Number, Vec<(usize, usize)>)> + '_ {
+        self.numbers.iter().map(|n| {
+            let symbols = (n.y - 1..=n.y + 1)
+                .flat_map(|y| (n.start - 1..=n.end).map(move |x| (y, x)))
+                .filter(|&(y, x)| !matches!(self.at(y, x), b'0'..=b'9' | b'.'))
+                .collect();
+            (n, symbols)
+        })
+    }
+
+    /// Returns the position of every `*` that is adjacent to a number, with those numbers.
+    fn gears(&self) -> HashMap<(usize, usize), Vec<&Number>> {
+        let mut map: HashMap<(usize, usize), Vec<&Number>> = HashMap::new();
+        for (n, symbols) in self.numbers() {
+            for (y, x) in symbols {
+                if self.at(y, x) == b'*' {
+                    map.entry((y, x)).or_default().push(n);
+                }
+            }
+        }
+        map
+    }
+
+    /// A frame of the schematic without its border, highlighting cells given in padded positions.
+    fn frame(
+        &self,
+        title: String,
+        highlights: impl IntoIterator<Item = ((usize, usize), Color)>,
+    ) -> Frame {
+        let inner = &self.rows[1..self.rows.len() - 1];
+        let text: String = inner
+            .iter()
+            .map(|row| String::from_utf8_lossy(&row[1..row.len() - 1]) + "\n")
+            .collect();
+        let mut grid = Grid::from_lines(&text);
+        for ((y, x), color) in highlights {
+            grid.highlight(x - 1, y - 1, color);
+        }
+        Frame::new(title).with_grid(grid)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +152,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(467835));
     }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
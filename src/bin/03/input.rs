@@ -0,0 +1,54 @@
+use advent_of_code::parse::{self, Span};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
+}
+
+/// Parses a rectangular grid of printable ASCII characters.
+pub fn parse(input: &str) -> parse::Result<Schematic> {
+    let lines: Vec<Span> = Span::new(input).lines().collect();
+    let width = lines.first().map_or(0, |line| line.as_str().len());
+    let mut rows = vec![vec![b'.'; width + 2]];
+    let mut numbers = vec![];
+
+    for line in lines {
+        let text = line.as_str();
+        if let Some((i, c)) = text.char_indices().find(|(_, c)| !c.is_ascii_graphic()) {
+            let span = line.slice(i..i + c.len_utf8());
+            return Err(span.error(format!("unexpected character {c:?}")));
+        }
+        if text.len() != width {
+            return Err(line.error(format!("expected {width} columns, found {}", text.len())));
+        }
+
+        let y = rows.len();
+        for m in NUM_RE.find_iter(text) {
+            numbers.push(Number {
+                value: line.slice(m.range()).number()?,
+                y,
+                start: m.start() + 1,
+                end: m.end() + 1,
+            });
+        }
+        rows.push([b".", text.as_bytes(), b"."].concat());
+    }
+
+    rows.push(vec![b'.'; width + 2]);
+    Ok(Schematic { rows, numbers })
+}
+
+/// The engine schematic, padded with a border of `.` so every number has neighbors on all sides.
+pub struct Schematic {
+    pub(super) rows: Vec<Vec<u8>>,
+    pub(super) numbers: Vec<Number>,
+}
+
+/// A number in the padded schematic, covering the columns `start..end` of row `y`.
+pub struct Number {
+    pub(super) value: u32,
+    pub(super) y: usize,
+    pub(super) start: usize,
+    pub(super) end: usize,
+}
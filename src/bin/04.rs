@@ -1,21 +1,19 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, validate = parse);
 
-use std::collections::{HashMap, HashSet};
+#[path = "04/input.rs"]
+mod input;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::collections::HashMap;
 
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
-    static ref GAME_NUM_RE: Regex = Regex::new(r"Card\s*(\d+)").unwrap();
-}
+use advent_of_code::{trace, trace_scope};
+use input::parse;
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
-        input
-            .split_terminator('\n')
-            .map(get_num_winning_in_line)
-            .map(|n| match n {
+        parse(input)
+            .unwrap_or_else(|e| panic!("{e}"))
+            .into_iter()
+            .map(|(_, n)| match n {
                 0 => 0,
                 _ => 2u32.pow(n - 1),
             })
@@ -25,12 +23,19 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut game_map: HashMap<u32, u32> = HashMap::new();
-    let res: u32 = input
-        .split_terminator('\n')
-        .map(|s| {
-            let game_num = GAME_NUM_RE.captures(s).unwrap()[1].parse::<u32>().unwrap();
-            let num_winning = get_num_winning_in_line(s);
+    let res: u32 = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
+        .map(|(game_num, num_winning)| {
             let num_copies = *game_map.get(&game_num).unwrap_or(&0) + 1;
+            trace_scope!("card {game_num} (x{num_copies})");
+            if num_winning > 0 {
+                trace!(
+                    "{num_winning} matching numbers: cards {} to {} gain {num_copies} each",
+                    game_num + 1,
+                    game_num + num_winning
+                );
+            }
 
             for i in game_num + 1..game_num + num_winning + 1 {
                 match game_map.get_mut(&i) {
@@ -47,23 +52,6 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(res)
 }
 
-fn get_num_winning_in_line(input: &str) -> u32 {
-    let input = &input[input.find(':').unwrap()..];
-    let split_pos = input.find('|').unwrap();
-    let winning_nums = &input[..split_pos];
-    let owned_nums = &input[split_pos..];
-
-    let mut winning_set = HashSet::new();
-    NUM_RE.captures_iter(winning_nums).for_each(|c| {
-        winning_set.insert(c[0].to_string());
-    });
-
-    NUM_RE
-        .captures_iter(owned_nums)
-        .filter(|c| winning_set.contains(&c[0]))
-        .count() as u32
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +67,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(30));
     }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
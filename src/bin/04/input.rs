@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use advent_of_code::parse::{self, Span};
+
+/// Returns `(card number, number of winning numbers)` for every card.
+pub fn parse(input: &str) -> parse::Result<Vec<(u32, u32)>> {
+    Span::new(input).lines().map(parse_card).collect()
+}
+
+fn parse_card(line: Span) -> parse::Result<(u32, u32)> {
+    let (game_num, numbers) = line.header("Card")?;
+    Ok((game_num.number()?, get_num_winning_in_line(numbers)?))
+}
+
+fn get_num_winning_in_line(numbers: Span) -> parse::Result<u32> {
+    let (winning_nums, owned_nums) = numbers.split_once("|")?;
+
+    let winning_set = winning_nums
+        .unsigned::<u32>()
+        .collect::<parse::Result<HashSet<_>>>()?;
+
+    let mut count = 0;
+    for num in owned_nums.unsigned::<u32>() {
+        if winning_set.contains(&num?) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
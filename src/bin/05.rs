@@ -1,42 +1,40 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, validate = parse);
 
-use std::cmp::Ordering;
+#[path = "05/input.rs"]
+mod input;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::cmp::Ordering;
 
-lazy_static! {
-    static ref MAP_RE: Regex =
-        Regex::new(r"(?<dest_start>\d+)\s+(?<source_start>\d+)\s+(?<range>\d+)").unwrap();
-    static ref DIGIT_RE: Regex = Regex::new(r"\d+").unwrap();
-    static ref TITLE_RE: Regex = Regex::new(r".*:").unwrap();
-    static ref SEEDS_PART2_RE: Regex = Regex::new(r"(?<start>\d+)\s+(?<range>\d+)").unwrap();
-}
+use advent_of_code::span;
+use advent_of_code::viz::{self, Color, Frame, Grid};
+use input::{parse, Map, MapLayer};
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let input = input.trim();
-    let (seeds_line, input) = input.split_once('\n').unwrap();
-
-    let layers: Vec<MapLayer> = TITLE_RE.split(input).map(MapLayer::from).collect();
+    let (seeds, layers) = {
+        let _s = span!("parse");
+        parse(input).unwrap_or_else(|e| panic!("{e}"))
+    };
+    let _s = span!("convert seeds");
 
-    let convert_seed = |seed: u32| {
+    let convert_seed = |seed: usize| {
         layers
             .iter()
             .fold(seed, |acc, layer: &MapLayer| layer.convert(acc))
     };
 
-    DIGIT_RE
-        .captures_iter(seeds_line)
-        // .par_bridge()
-        .map(|c| c[0].parse::<u32>().unwrap())
+    seeds
+        .into_iter()
         .map(convert_seed)
         .min()
+        .and_then(|location| location.try_into().ok())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let input = input.trim();
-    let (seeds_line, input) = input.split_once('\n').unwrap();
-    let layers: Vec<MapLayer> = TITLE_RE.split(input).map(MapLayer::from).collect();
+    let (seeds, layers) = {
+        let _s = span!("parse");
+        parse(input).unwrap_or_else(|e| panic!("{e}"))
+    };
+    let _s = span!("propagate ranges");
 
     let convert_seed = |seed: SeedRange, layers: &Vec<MapLayer>| {
         layers
@@ -52,65 +50,73 @@ pub fn part_two(input: &str) -> Option<usize> {
             .unwrap()
     };
 
-    let res = SEEDS_PART2_RE
-        .captures_iter(seeds_line)
-        .map(|c| {
-            let start = c["start"].parse::<usize>().unwrap();
-            let range = c["range"].parse::<usize>().unwrap();
-            SeedRange {
-                start,
-                end: start + range - 1,
-            }
-            // let layers = layers.clone();
-            // thread::spawn(move || convert_seed(seed, &layers))
+    let seeds: Vec<SeedRange> = seeds
+        .chunks_exact(2)
+        .map(|pair| SeedRange {
+            start: pair[0],
+            end: pair[0] + pair[1] - 1,
         })
-        // .par_bridge()
+        .collect();
+
+    if viz::enabled() {
+        visualize(&seeds, &layers);
+    }
+
+    seeds
+        .into_iter()
         .map(|seed| convert_seed(seed, &layers))
-        .min();
-    // .collect();
-    //
-    // let res = thread_handles
-    //     .into_iter()
-    //     .map(|handle| handle.join().unwrap())
-    //     .min()
-    //     .unwrap();
-    res
-    // None
+        .min()
 }
 
-#[derive(Debug, Clone)]
-struct MapLayer(Vec<Map>);
+const VIZ_WIDTH: usize = 64;
 
-#[derive(Debug, Clone, Copy)]
-struct Map {
-    source_start: usize,
-    dest_start: usize,
-    range: usize,
-}
+/// Emits a frame with the seed ranges, then one per layer with the ranges it split them into.
+fn visualize(seeds: &[SeedRange], layers: &[MapLayer]) {
+    let mut ranges: Vec<(SeedRange, bool)> = seeds.iter().map(|s| (s.clone(), false)).collect();
+    viz::emit(|| ranges_frame("Part 2: seed ranges".to_string(), &ranges));
 
-#[derive(Debug, Clone)]
-struct SeedRange {
-    start: usize,
-    // range: usize,
-    end: usize,
+    for (i, layer) in layers.iter().enumerate() {
+        ranges = ranges
+            .iter()
+            .flat_map(|(s, _)| layer.split(s.clone()))
+            .map(|s| match layer.map_range(&s) {
+                Some(mapped) => (mapped, true),
+                None => (s, false),
+            })
+            .collect();
+        let title = format!("Part 2: after layer {}/{}", i + 1, layers.len());
+        viz::emit(|| ranges_frame(title, &ranges));
+    }
 }
 
-impl From<&str> for Map {
-    fn from(value: &str) -> Self {
-        let caps = MAP_RE.captures(value).expect("Invalid Map String");
-        Map {
-            source_start: caps["source_start"].parse::<usize>().unwrap(),
-            dest_start: caps["dest_start"].parse::<usize>().unwrap(),
-            range: caps["range"].parse::<usize>().unwrap(),
+/// Draws every range as a bar on a number line from the lowest start to the highest end.
+fn ranges_frame(title: String, ranges: &[(SeedRange, bool)]) -> Frame {
+    let lo = ranges.iter().map(|(s, _)| s.start).min().unwrap_or(0);
+    let hi = ranges.iter().map(|(s, _)| s.end).max().unwrap_or(0);
+    let column =
+        |v: usize| ((v - lo) as u128 * (VIZ_WIDTH - 1) as u128 / (hi - lo).max(1) as u128) as usize;
+
+    let mut grid = Grid::new(VIZ_WIDTH, ranges.len(), '.');
+    for (y, (s, mapped)) in ranges.iter().enumerate() {
+        let color = if *mapped { Color::Green } else { Color::Gray };
+        for x in column(s.start)..=column(s.end) {
+            grid.set(x, y, '#');
+            grid.highlight(x, y, color);
         }
     }
+
+    Frame::new(title)
+        .with_grid(grid)
+        .label("mapped", Color::Green)
+        .label("passed through", Color::Gray)
+        .note(format!("{} ranges from {lo} to {hi}", ranges.len()))
 }
 
-impl From<&str> for MapLayer {
-    fn from(value: &str) -> Self {
-        let value = value.trim();
-        MapLayer(value.lines().map(Map::from).collect())
-    }
+#[derive(Debug, Clone)]
+struct SeedRange {
+    start: usize,
+    // range: usize,
+    end: usize,
 }
 
 impl Map {
@@ -124,7 +130,12 @@ impl Map {
             s.start.cmp(&m_end),
             s.end.cmp(&m_end),
         ) {
-            (Ordering::Greater | Ordering::Equal, _, Ordering::Less, Ordering::Greater) => {
+            (
+                Ordering::Greater | Ordering::Equal,
+                _,
+                Ordering::Less | Ordering::Equal,
+                Ordering::Greater,
+            ) => {
                 vec![
                     SeedRange {
                         start: s.start,
@@ -137,7 +148,12 @@ impl Map {
                 ]
             }
 
-            (Ordering::Less, Ordering::Greater, _, Ordering::Less | Ordering::Equal) => {
+            (
+                Ordering::Less,
+                Ordering::Greater | Ordering::Equal,
+                _,
+                Ordering::Less | Ordering::Equal,
+            ) => {
                 vec![
                     SeedRange {
                         start: s.start,
@@ -183,35 +199,37 @@ impl Map {
 }
 
 impl MapLayer {
-    fn convert(&self, input: u32) -> u32 {
+    fn convert(&self, input: usize) -> usize {
         for map in &self.0 {
-            if (map.source_start..map.source_start + map.range).contains(&(input as usize)) {
-                return (map.dest_start + (input as usize - map.source_start)) as u32;
+            if (map.source_start..map.source_start + map.range).contains(&input) {
+                return map.dest_start + (input - map.source_start);
             }
         }
         input
     }
 
     fn convert_range(&self, input: SeedRange) -> Vec<SeedRange> {
-        let splitted = self.0.iter().fold(vec![input], |acc, m| {
-            acc.into_iter()
-                .flat_map(|seed| m.split_range(&seed))
-                .collect()
-        });
-
         // we split over all the maps in the layer before we start converting to avoid
         // re-converting/re-splitting ranges
         //
-        let helper_fn = |s: SeedRange| {
-            for map in &self.0 {
-                if let Some(new_range) = map.convert_range(&s) {
-                    return new_range;
-                }
-            }
-            s
-        };
+        self.split(input)
+            .into_iter()
+            .map(|s| self.map_range(&s).unwrap_or(s))
+            .collect()
+    }
 
-        splitted.into_iter().map(helper_fn).collect()
+    /// Splits `input` into ranges that each lie completely inside or outside of every map.
+    fn split(&self, input: SeedRange) -> Vec<SeedRange> {
+        self.0.iter().fold(vec![input], |acc, m| {
+            acc.into_iter()
+                .flat_map(|seed| m.split_range(&seed))
+                .collect()
+        })
+    }
+
+    /// Converts a range returned by [`Self::split`], or `None` if no map covers it.
+    fn map_range(&self, s: &SeedRange) -> Option<SeedRange> {
+        self.0.iter().find_map(|map| map.convert_range(s))
     }
 }
 #[cfg(test)]
@@ -229,4 +247,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
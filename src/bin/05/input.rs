@@ -0,0 +1,65 @@
+use advent_of_code::parse::{self, Span};
+
+/// Splits the input into the `seeds:` numbers and one [`MapLayer`] per map section.
+pub fn parse(input: &str) -> parse::Result<(Vec<usize>, Vec<MapLayer>)> {
+    let mut sections = Span::new(input).sections();
+    let (_, seeds) = sections
+        .next()
+        .ok_or_else(|| Span::new(input).error("expected `seeds:`"))?
+        .header("seeds")?;
+    let layers = sections
+        .map(MapLayer::parse)
+        .collect::<parse::Result<_>>()?;
+
+    let seed_values = seeds
+        .unsigned::<usize>()
+        .collect::<parse::Result<Vec<_>>>()?;
+    if seed_values.len() % 2 != 0 {
+        return Err(seeds.error("expected pairs of seed start and range"));
+    }
+    for pair in seed_values.chunks_exact(2) {
+        if pair[1] == 0 || pair[0].checked_add(pair[1]).is_none() {
+            return Err(seeds.error(format!("invalid seed range {} {}", pair[0], pair[1])));
+        }
+    }
+
+    Ok((seed_values, layers))
+}
+
+#[derive(Debug, Clone)]
+pub struct MapLayer(pub(super) Vec<Map>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Map {
+    pub(super) source_start: usize,
+    pub(super) dest_start: usize,
+    pub(super) range: usize,
+}
+
+impl Map {
+    /// Parses a line like `50 98 2`.
+    fn parse(line: Span) -> parse::Result<Self> {
+        let [dest_start, source_start, range]: [usize; 3] = line.unsigned().array()?;
+        if range == 0 || dest_start.max(source_start).checked_add(range).is_none() {
+            return Err(line.error("map range must be non-empty and fit into usize"));
+        }
+        Ok(Map {
+            source_start,
+            dest_start,
+            range,
+        })
+    }
+}
+
+impl MapLayer {
+    /// Parses a section like `seed-to-soil map:` followed by one map per line.
+    fn parse(section: Span) -> parse::Result<Self> {
+        let (_, maps) = section.split_once(":")?;
+        Ok(MapLayer(
+            maps.trim()
+                .lines()
+                .map(Map::parse)
+                .collect::<parse::Result<_>>()?,
+        ))
+    }
+}
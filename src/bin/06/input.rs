@@ -0,0 +1,53 @@
+use advent_of_code::parse::{self, Span};
+
+/// Returns the races of part one, and the single race of part two that ignores the spaces
+/// between the numbers.
+pub fn parse(input: &str) -> parse::Result<(Vec<Race>, Race)> {
+    let (time_str, dist_str) = Span::new(input).split_once("\n")?;
+    let (_, time_str) = time_str.header("Time")?;
+    let (_, dist_str) = dist_str.header("Distance")?;
+
+    let times = time_str
+        .unsigned::<u64>()
+        .collect::<parse::Result<Vec<_>>>()?;
+    let distances = dist_str
+        .unsigned::<u64>()
+        .collect::<parse::Result<Vec<_>>>()?;
+    if times.len() != distances.len() {
+        return Err(dist_str.error(format!(
+            "expected {} distances, found {}",
+            times.len(),
+            distances.len()
+        )));
+    }
+    let races = times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect();
+
+    let joined = |numbers: Span| {
+        let digits: String = numbers
+            .as_str()
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse::<u64>().map_err(|e| {
+            numbers
+                .trim()
+                .error(format!("invalid number `{digits}`: {e}"))
+        })
+    };
+    let race = Race {
+        time: joined(time_str)?,
+        distance: joined(dist_str)?,
+    };
+
+    Ok((races, race))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Race {
+    pub(super) time: u64,
+    pub(super) distance: u64,
+}
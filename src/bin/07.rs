@@ -1,41 +1,55 @@
-use itertools::Itertools;
+use advent_of_code::trace;
+use input::parse;
 use std::collections::HashMap;
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, validate = parse);
+
+#[path = "07/input.rs"]
+mod input;
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut card_bid_pairs: Vec<(Hand, u32)> = input
-        .trim()
-        .lines()
-        .map(|l| l.trim())
-        .map(|l| l.split_whitespace().next_tuple().unwrap())
-        .map(|tup: (&str, &str)| (Hand::from_str_p1(tup.0), tup.1.parse::<u32>().unwrap()))
+    let mut card_bid_pairs: Vec<(Hand, u32, &str)> = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
+        .map(|(cards, bid)| (Hand::from_str_p1(cards), bid, cards))
         .collect();
 
-    card_bid_pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    card_bid_pairs.sort_by_key(|a| a.0);
 
-    let res = card_bid_pairs
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, pair)| ((i as u32 + 1) * pair.1) + acc);
+    let res = card_bid_pairs.iter().enumerate().fold(0, |acc, (i, pair)| {
+        trace!(
+            "rank {}: {} is {:?}, wins {} * {}",
+            i + 1,
+            pair.2,
+            pair.0.hand_type,
+            i + 1,
+            pair.1
+        );
+        ((i as u32 + 1) * pair.1) + acc
+    });
 
     Some(res)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut card_bid_pairs: Vec<(Hand, u32)> = input
-        .trim()
-        .lines()
-        .map(|l| l.trim())
-        .map(|l| l.split_whitespace().next_tuple().unwrap())
-        .map(|tup: (&str, &str)| (Hand::from(tup.0), tup.1.parse::<u32>().unwrap()))
+    let mut card_bid_pairs: Vec<(Hand, u32, &str)> = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
+        .map(|(cards, bid)| (Hand::from(cards), bid, cards))
         .collect();
 
-    card_bid_pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    card_bid_pairs.sort_by_key(|a| a.0);
 
-    let res = card_bid_pairs
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, pair)| ((i as u32 + 1) * pair.1) + acc);
+    let res = card_bid_pairs.iter().enumerate().fold(0, |acc, (i, pair)| {
+        trace!(
+            "rank {}: {} is {:?}, wins {} * {}",
+            i + 1,
+            pair.2,
+            pair.0.hand_type,
+            i + 1,
+            pair.1
+        );
+        ((i as u32 + 1) * pair.1) + acc
+    });
 
     Some(res)
 }
@@ -81,7 +95,7 @@ impl Hand {
                 'Q' => 12,
                 'K' => 13,
                 'A' => 14,
-                _ => unimplemented!(),
+                _ => unreachable!("cards are validated by `parse`"),
             }
         };
         let cards: Vec<u8> = value.trim().chars().map(char_to_val).collect();
@@ -100,19 +114,19 @@ impl Hand {
             2 => match occ[..] {
                 [1, 4] => HandType::FourOK,
                 [2, 3] => HandType::FullHouse,
-                _ => unimplemented!(),
+                _ => unreachable!("a hand has five cards"),
             },
             3 => match occ[..] {
                 [1, 1, 3] => HandType::ThreeOK,
                 [1, 2, 2] => HandType::TwoPair,
-                _ => unimplemented!(),
+                _ => unreachable!("a hand has five cards"),
             },
 
             4 => HandType::OnePair,
 
             5 => HandType::HighCard,
 
-            _ => unimplemented!(),
+            _ => unreachable!("a hand has five cards"),
         };
 
         Hand {
@@ -136,7 +150,7 @@ impl From<&str> for Hand {
                 'Q' => 12,
                 'K' => 13,
                 'A' => 14,
-                _ => unimplemented!(),
+                _ => unreachable!("cards are validated by `parse`"),
             }
         };
         let cards: Vec<u8> = value.trim().chars().map(char_to_val).collect();
@@ -168,19 +182,19 @@ impl From<&str> for Hand {
             2 => match occ[..] {
                 [1, 4] => HandType::FourOK,
                 [2, 3] => HandType::FullHouse,
-                _ => unimplemented!(),
+                _ => unreachable!("a hand has five cards"),
             },
             3 => match occ[..] {
                 [1, 1, 3] => HandType::ThreeOK,
                 [1, 2, 2] => HandType::TwoPair,
-                _ => unimplemented!(),
+                _ => unreachable!("a hand has five cards"),
             },
 
             4 => HandType::OnePair,
 
             5 => HandType::HighCard,
 
-            _ => unimplemented!(),
+            _ => unreachable!("a hand has five cards"),
         };
 
         Hand {
@@ -205,4 +219,14 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5905));
     }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
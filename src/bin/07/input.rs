@@ -0,0 +1,26 @@
+use advent_of_code::parse::{self, Span};
+
+/// Returns the cards and the bid of every hand. Cards are validated, so they can be converted
+/// into a [`Hand`](super::Hand) for either part.
+pub fn parse(input: &str) -> parse::Result<Vec<(&str, u32)>> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut tokens = line.tokens();
+            let (Some(cards), Some(bid), None) = (tokens.next(), tokens.next(), tokens.next())
+            else {
+                return Err(line.trim().error("expected cards and a bid"));
+            };
+            if cards.as_str().chars().count() != 5 {
+                return Err(cards.error("expected 5 cards"));
+            }
+            if let Some(card) = cards.as_str().chars().find(|&c| !CARDS.contains(c)) {
+                return Err(cards.error(format!("unknown card `{card}`")));
+            }
+            Ok((cards.as_str(), bid.number()?))
+        })
+        .collect()
+}
+
+const CARDS: &str = "23456789TJQKA";
@@ -1,40 +1,32 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, validate = parse, graph = graph);
+
+#[path = "08/input.rs"]
+mod input;
+
 use std::collections::HashMap;
 
-use lazy_static::lazy_static;
+use advent_of_code::span;
+use advent_of_code::viz::dot::Graph;
+use advent_of_code::viz::Color;
+use input::parse;
 use rayon::prelude::*;
-use regex::Regex;
 
-lazy_static! {
-    static ref MAP_RE: Regex =
-        Regex::new(r"(?<Location>\w+)\s*=\s*\((?<Left>\w+)\s*,\s*(?<Right>\w+)\)").unwrap();
-}
 pub fn part_one(input: &str) -> Option<u64> {
-    let (instructions, map_str) = input.split_once('\n').unwrap();
-    let instructions = instructions.trim();
-    let map: HashMap<String, (String, String)> = MAP_RE
-        .captures_iter(map_str)
-        .map(|c| {
-            (
-                c["Location"].to_owned(),
-                (c["Left"].to_owned(), c["Right"].to_owned()),
-            )
-        })
-        .collect();
+    let (instructions, network) = {
+        let _s = span!("parse");
+        parse(input).unwrap_or_else(|e| panic!("{e}"))
+    };
+    let _s = span!("walk");
+    let start = network.interner.get("AAA")?;
+    let goal = network.interner.get("ZZZ")?;
 
-    let mut cur = &"AAA".to_owned();
-    let zzz = "ZZZ";
+    let mut cur = start;
     let steps = instructions
-        .chars()
+        .iter()
         .cycle()
-        .take_while(|&dir| {
-            let next = match dir {
-                'R' => &map.get(cur).unwrap().1,
-                'L' => &map.get(cur).unwrap().0,
-                _ => unimplemented!(),
-            };
-            cur = next;
-            cur != zzz
+        .take_while(|&&side| {
+            cur = network.step(cur, side);
+            cur != goal
         })
         .count() as u64
         + 1;
@@ -42,35 +34,26 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (instructions, map_str) = input.split_once('\n').unwrap();
-    let instructions = instructions.trim();
-    let map: HashMap<String, (String, String)> = MAP_RE
-        .captures_iter(map_str)
-        .map(|c| {
-            (
-                c["Location"].to_owned(),
-                (c["Left"].to_owned(), c["Right"].to_owned()),
-            )
-        })
-        .collect();
+    let (instructions, network) = {
+        let _s = span!("parse");
+        parse(input).unwrap_or_else(|e| panic!("{e}"))
+    };
+    let _s = span!("walks");
+    let starts = network.select(|label| label.ends_with('A'));
+    let goals = network.select(|label| label.ends_with('Z'));
 
-    let res = map
-        .keys()
-        .filter(|key| key.ends_with('A'))
+    let res = starts
+        .iter()
         .par_bridge()
-        .map(|start: &String| {
-            let mut cur = start.clone();
-            let cloned_map = map.clone();
+        .map(|start| {
+            let _s = span!("walk");
+            let mut cur = start;
             instructions
-                .chars()
+                .iter()
                 .cycle()
-                .take_while(move |dir| {
-                    cur = match dir {
-                        'R' => cloned_map.get(&cur).unwrap().1.clone(),
-                        'L' => cloned_map.get(&cur).unwrap().0.clone(),
-                        _ => unimplemented!(),
-                    };
-                    !cur.ends_with('Z')
+                .take_while(|&&side| {
+                    cur = network.step(cur, side);
+                    !goals.contains(cur)
                 })
                 .count() as u64
                 + 1
@@ -78,7 +61,76 @@ pub fn part_two(input: &str) -> Option<u64> {
         .reduce(|| 1, lcm);
 
     Some(res)
-    // None
+}
+
+const CYCLE_COLORS: [Color; 4] = [Color::Blue, Color::Magenta, Color::Cyan, Color::Yellow];
+
+/// Builds the network as a graph for `cargo explore 08 --dot <path>`. Starts (`..A`) are green and
+/// goals (`..Z`) red. The cycle that the walk from each start ends up in is drawn in its own color,
+/// with notes on where the goals are, since part two relies on one goal per cycle.
+pub fn graph(input: &str) -> Graph {
+    let (instructions, network) = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    let label = |id: u32| network.interner.label(id);
+    let side_label = |node: u32, side: usize| {
+        let [left, right] = network.edges[node as usize];
+        match (left == right, side) {
+            (true, _) => "L/R",
+            (false, 0) => "L",
+            (false, _) => "R",
+        }
+    };
+
+    let mut graph = Graph::new(DAY.to_string());
+    for (id, name) in network.interner.iter() {
+        let [left, right] = network.edges[id as usize];
+        graph.edge(name, label(left), side_label(id, 0));
+        if left != right {
+            graph.edge(name, label(right), side_label(id, 1));
+        }
+    }
+
+    let starts = network.select(|label| label.ends_with('A'));
+    let goals = network.select(|label| label.ends_with('Z'));
+    for id in goals.iter() {
+        graph.highlight(label(id), Color::Red);
+    }
+
+    for (n, start) in starts.iter().enumerate() {
+        let color = CYCLE_COLORS[n % CYCLE_COLORS.len()];
+        graph.highlight(label(start), Color::Green);
+
+        // walk until a (node, instruction) state repeats, the walk loops from there on.
+        let mut seen: HashMap<(u32, usize), usize> = HashMap::new();
+        let mut path = vec![];
+        let mut cur = start;
+        let cycle_start = loop {
+            let i = path.len() % instructions.len();
+            if let Some(&step) = seen.get(&(cur, i)) {
+                break step;
+            }
+            seen.insert((cur, i), path.len());
+            path.push(cur);
+            cur = network.step(cur, instructions[i]);
+        };
+
+        for step in cycle_start..path.len() {
+            let (node, side) = (path[step], instructions[step % instructions.len()]);
+            let next = path.get(step + 1).copied().unwrap_or(cur);
+            graph.outline(label(node), color);
+            graph.color_edge(label(node), label(next), side_label(node, side), color);
+        }
+
+        let goal_steps: Vec<usize> = (0..path.len())
+            .filter(|&step| goals.contains(path[step]))
+            .collect();
+        graph.note(format!(
+            "{}: enters a cycle of {} steps after {cycle_start} steps, goals at steps {goal_steps:?}",
+            label(start),
+            path.len() - cycle_start,
+        ));
+    }
+
+    graph
 }
 
 fn gcd(a: u64, b: u64) -> u64 {
@@ -112,4 +164,27 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_graph() {
+        let graph = graph(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(graph.node_count(), 8);
+        assert_eq!(
+            graph.notes(),
+            [
+                "11A: enters a cycle of 2 steps after 1 steps, goals at steps [2]",
+                "22A: enters a cycle of 6 steps after 1 steps, goals at steps [3, 6]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
+    }
 }
@@ -0,0 +1,44 @@
+use advent_of_code::interner::Network;
+use advent_of_code::parse::{self, Span};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref MAP_RE: Regex =
+        Regex::new(r"^(?<Location>\w+)\s*=\s*\((?<Left>\w+)\s*,\s*(?<Right>\w+)\)$").unwrap();
+}
+
+/// Returns the instructions (`0` for left, `1` for right) and the network of nodes.
+pub fn parse(input: &str) -> parse::Result<(Vec<usize>, Network<'_>)> {
+    let (instructions, map_str) = Span::new(input).split_once("\n")?;
+    let instructions = instructions.trim();
+    if instructions.is_empty() {
+        return Err(instructions.error("expected instructions"));
+    }
+    let instructions = instructions
+        .as_str()
+        .char_indices()
+        .map(|(i, dir)| match dir {
+            'L' => Ok(0),
+            'R' => Ok(1),
+            _ => Err(instructions
+                .slice(i..i + dir.len_utf8())
+                .error(format!("expected `L` or `R`, found `{dir}`"))),
+        })
+        .collect::<parse::Result<_>>()?;
+
+    let entries = map_str
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let c = MAP_RE
+                .captures(line.as_str())
+                .ok_or_else(|| line.error("expected a node like `AAA = (BBB, CCC)`"))?;
+            let (_, [location, left, right]) = c.extract();
+            Ok((location, [left, right]))
+        })
+        .collect::<parse::Result<Vec<_>>>()?;
+
+    Ok((instructions, Network::from_entries(entries)))
+}
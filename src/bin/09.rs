@@ -1,54 +1,27 @@
-use itertools::Itertools;
+use advent_of_code::bigint::BigInt;
+use input::parse;
 
-advent_of_code::solution!(9);
+#[path = "09/input.rs"]
+mod input;
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let res = input
-        .trim()
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| {
-                    s.parse::<i32>()
-                        .unwrap_or_else(|_| panic!("{} is not a number", s))
-                })
-                .rev()
-                .collect()
-        })
-        .map(get_next)
-        .sum();
-    Some(res)
-}
+advent_of_code::solution!(9, validate = parse);
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let res = input
-        .trim()
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| {
-                    s.parse::<i32>()
-                        .unwrap_or_else(|_| panic!("{} is not a number", s))
-                })
-                // .rev()
-                .collect()
-        })
-        .map(get_next)
-        .sum();
-    Some(res)
+pub fn part_one(input: &str) -> Option<BigInt> {
+    let sum = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .iter()
+        .map(|p| p.forward(1).map(BigInt::from))
+        .sum::<Result<_, _>>();
+    Some(sum.unwrap_or_else(|e| panic!("{e}")))
 }
-fn get_next(input: Vec<i32>) -> i32 {
-    if let (Some(0) | None, Some(0) | None) = (input.last(), input.first()) {
-        return 0;
-    }
-    let end = *input.first().unwrap_or(&0);
 
-    let diffs: Vec<i32> = input
-        .into_iter()
-        .tuple_windows()
-        .map(|(a, b)| a - b)
-        .collect();
-    end + get_next(diffs)
+pub fn part_two(input: &str) -> Option<BigInt> {
+    let sum = parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .iter()
+        .map(|p| p.backward(1).map(BigInt::from))
+        .sum::<Result<_, _>>();
+    Some(sum.unwrap_or_else(|e| panic!("{e}")))
 }
 
 #[cfg(test)]
@@ -58,12 +31,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+        assert_eq!(result, Some(BigInt::from(114)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Some(BigInt::from(2)));
+    }
+
+    #[test]
+    fn test_against_reference() {
+        advent_of_code::generate::check(DAY, part_one, part_two);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        advent_of_code::generate::fuzz(DAY, |input| parse(input).map(drop));
     }
 }
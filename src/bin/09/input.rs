@@ -0,0 +1,17 @@
+use advent_of_code::parse::{self, Span};
+use advent_of_code::polynomial::Polynomial;
+
+/// Fits a [`Polynomial`] through the numbers of every line.
+pub fn parse(input: &str) -> parse::Result<Vec<Polynomial>> {
+    Span::new(input)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let values = line
+                .tokens()
+                .map(Span::number::<i64>)
+                .collect::<parse::Result<Vec<_>>>()?;
+            Polynomial::fit(&values).map_err(|e| line.error(e.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,144 @@
+/// Checked arithmetic on primitive integers that reports which operation overflowed.
+///
+/// The standard `checked_*` methods only return `None`. The helpers here return an [`Overflow`]
+/// that names the operands and the type, so a solution can fail with a useful message or switch
+/// to a wider type or a [`crate::bigint::BigInt`].
+use std::error::Error as StdError;
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    /// The operator, e.g. `+`.
+    pub op: char,
+    pub lhs: String,
+    pub rhs: String,
+    /// The type of the operands, e.g. `u64`.
+    pub ty: &'static str,
+}
+
+impl StdError for Overflow {}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} overflows {}, use a wider type or a BigInt.",
+            self.lhs, self.op, self.rhs, self.ty
+        )
+    }
+}
+
+/// A primitive integer with checked arithmetic.
+pub trait CheckedInt: Copy + Display {
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! checked_int {
+    ($($t:ty),*) => {$(
+        impl CheckedInt for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+checked_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn check<T: CheckedInt>(op: char, lhs: T, rhs: T, result: Option<T>) -> Result<T, Overflow> {
+    result.ok_or_else(|| Overflow {
+        op,
+        lhs: lhs.to_string(),
+        rhs: rhs.to_string(),
+        ty: T::NAME,
+    })
+}
+
+pub fn add<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, Overflow> {
+    check('+', lhs, rhs, lhs.checked_add(rhs))
+}
+
+pub fn sub<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, Overflow> {
+    check('-', lhs, rhs, lhs.checked_sub(rhs))
+}
+
+pub fn mul<T: CheckedInt>(lhs: T, rhs: T) -> Result<T, Overflow> {
+    check('*', lhs, rhs, lhs.checked_mul(rhs))
+}
+
+/// Checked versions of [`Iterator::sum`] and [`Iterator::product`].
+pub trait CheckedIterator: Iterator {
+    /// The sum of the items, or the first addition that overflowed.
+    fn checked_sum(self) -> Result<Self::Item, Overflow>;
+    /// The product of the items, or the first multiplication that overflowed.
+    fn checked_product(self) -> Result<Self::Item, Overflow>;
+}
+
+impl<I: Iterator<Item = T>, T: CheckedInt> CheckedIterator for I {
+    fn checked_sum(mut self) -> Result<T, Overflow> {
+        self.try_fold(T::ZERO, add)
+    }
+
+    fn checked_product(mut self) -> Result<T, Overflow> {
+        self.try_fold(T::ONE, mul)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{add, mul, sub, CheckedIterator, Overflow};
+
+    #[test]
+    fn reports_overflowing_operation() {
+        assert_eq!(add(1_u8, 2), Ok(3));
+        assert_eq!(
+            add(200_u8, 100),
+            Err(Overflow {
+                op: '+',
+                lhs: "200".into(),
+                rhs: "100".into(),
+                ty: "u8"
+            })
+        );
+        assert_eq!(
+            sub(0_u32, 1).unwrap_err().to_string(),
+            "0 - 1 overflows u32, use a wider type or a BigInt."
+        );
+        assert_eq!(mul(-3_i64, 4), Ok(-12));
+        assert!(mul(i64::MIN, -1).is_err());
+    }
+
+    #[test]
+    fn sums_and_multiplies_iterators() {
+        assert_eq!([1_u64, 2, 3].into_iter().checked_sum(), Ok(6));
+        assert_eq!(std::iter::empty::<i32>().checked_sum(), Ok(0));
+        assert_eq!(
+            (1..=20_u64).checked_product(),
+            Ok(2_432_902_008_176_640_000)
+        );
+        assert_eq!(
+            (1..=21_u64).checked_product().unwrap_err().to_string(),
+            "2432902008176640000 * 21 overflows u64, use a wider type or a BigInt."
+        );
+    }
+}
@@ -0,0 +1,212 @@
+/// Structured steps that solutions trace to explain how they got to an answer.
+///
+/// Solutions call [`trace!`](crate::trace) for single steps and [`trace_scope!`](crate::trace_scope)
+/// to group the steps until the end of the enclosing block under a heading. Steps are only
+/// formatted and recorded if the crate is compiled with the `explain` feature and the solution runs
+/// with `--explain` (`cargo solve <day> --explain`). Otherwise both macros call empty functions and
+/// their arguments are never formatted, so benchmarks are unaffected.
+///
+/// Steps are recorded in the order they are traced, so traces from parallel code interleave.
+use std::fmt::Write as _;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// A traced line, indented by the number of enclosing scopes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub depth: usize,
+    pub text: String,
+}
+
+/// Groups the steps traced while it is alive, see [`trace_scope!`](crate::trace_scope).
+#[must_use = "a scope ends when it is dropped"]
+pub struct Scope {
+    #[cfg(feature = "explain")]
+    active: bool,
+}
+
+/// Records a step while a part runs with `--explain`, e.g. `trace!("game {id} is impossible")`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::explain::step(|| format!($($arg)*))
+    };
+}
+
+/// Records a heading and indents the steps traced after it until the end of the enclosing block.
+/// Use one per block, a second one in the same block nests inside the first.
+#[macro_export]
+macro_rules! trace_scope {
+    ($($arg:tt)*) => {
+        let _scope = $crate::explain::scope(|| format!($($arg)*));
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "explain")]
+mod recorder {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    use super::{Scope, Step};
+
+    static RECORDING: AtomicBool = AtomicBool::new(false);
+    static STEPS: Mutex<(usize, Vec<Step>)> = Mutex::new((0, Vec::new()));
+
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    pub fn step(text: impl FnOnce() -> String) {
+        if RECORDING.load(Ordering::Relaxed) {
+            let text = text();
+            let (depth, steps) = &mut *STEPS.lock().unwrap();
+            steps.push(Step {
+                depth: *depth,
+                text,
+            });
+        }
+    }
+
+    pub fn scope(heading: impl FnOnce() -> String) -> Scope {
+        let active = RECORDING.load(Ordering::Relaxed);
+        if active {
+            step(heading);
+            STEPS.lock().unwrap().0 += 1;
+        }
+        Scope { active }
+    }
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            if self.active {
+                let depth = &mut STEPS.lock().unwrap().0;
+                *depth = depth.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Step>) {
+        if !enabled() {
+            return (f(), vec![]);
+        }
+        *STEPS.lock().unwrap() = (0, vec![]);
+        RECORDING.store(true, Ordering::Relaxed);
+        let res = f();
+        RECORDING.store(false, Ordering::Relaxed);
+        (res, std::mem::take(&mut STEPS.lock().unwrap().1))
+    }
+}
+
+/// Records the step built by `text`, see [`trace!`](crate::trace).
+#[cfg(feature = "explain")]
+pub fn step(text: impl FnOnce() -> String) {
+    recorder::step(text);
+}
+
+/// Records the step built by `text`, see [`trace!`](crate::trace).
+#[cfg(not(feature = "explain"))]
+#[inline(always)]
+pub fn step(_text: impl FnOnce() -> String) {}
+
+/// Records a heading and returns a guard that indents the following steps until it is dropped.
+#[cfg(feature = "explain")]
+pub fn scope(heading: impl FnOnce() -> String) -> Scope {
+    recorder::scope(heading)
+}
+
+/// Records a heading and returns a guard that indents the following steps until it is dropped.
+#[cfg(not(feature = "explain"))]
+#[inline(always)]
+pub fn scope(_heading: impl FnOnce() -> String) -> Scope {
+    Scope {}
+}
+
+/// Turns explain mode on or off for the parts that run after, as `--explain` does.
+pub fn set_enabled(enabled: bool) {
+    #[cfg(feature = "explain")]
+    recorder::set_enabled(enabled);
+    #[cfg(not(feature = "explain"))]
+    let _ = enabled;
+}
+
+/// Runs `f` and returns the steps it traced. Returns no steps if explain mode is off.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Step>) {
+    #[cfg(feature = "explain")]
+    return recorder::record(f);
+    #[cfg(not(feature = "explain"))]
+    return (f(), vec![]);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the steps of a part, indented by depth. Headings of scopes are bold.
+#[must_use]
+pub fn render(steps: &[Step], part: u8) -> String {
+    if steps.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("{ANSI_ITALIC}Part {part} explained:{ANSI_RESET}\n");
+    for (i, step) in steps.iter().enumerate() {
+        let indent = "  ".repeat(step.depth + 1);
+        let is_heading = steps.get(i + 1).is_some_and(|next| next.depth > step.depth);
+        if is_heading {
+            let _ = writeln!(out, "{indent}{ANSI_BOLD}{}{ANSI_RESET}", step.text);
+        } else {
+            let _ = writeln!(out, "{indent}{}", step.text);
+        }
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Step};
+
+    #[test]
+    fn renders_grouped_steps() {
+        let steps = [
+            Step {
+                depth: 0,
+                text: "game 3 is impossible".into(),
+            },
+            Step {
+                depth: 1,
+                text: "20 red cubes, but only 12 in the bag".into(),
+            },
+            Step {
+                depth: 0,
+                text: "sum: 8".into(),
+            },
+        ];
+        assert_eq!(
+            render(&steps, 1),
+            "\x1b[3mPart 1 explained:\x1b[0m\n  \
+             \x1b[1mgame 3 is impossible\x1b[0m\n    \
+             20 red cubes, but only 12 in the bag\n  \
+             sum: 8\n"
+        );
+        assert_eq!(render(&[], 1), "");
+    }
+
+    #[test]
+    fn records_nothing_without_explain_flag() {
+        let (res, steps) = super::record(|| {
+            crate::trace_scope!("game {}", 1);
+            crate::trace!("step {}", 2);
+            3
+        });
+        assert_eq!(res, 3);
+        assert!(steps.is_empty());
+    }
+}
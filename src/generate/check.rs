@@ -0,0 +1,239 @@
+/// Differential testing of optimized solutions against the brute-force reference solvers.
+///
+/// [`check`] runs a day's `part_one` and `part_two` against many small generated inputs and
+/// compares them with the references. A failing input is shrunk and written to the `regressions`
+/// data folder (`data/regressions/` by default), where it is replayed by every following run.
+use std::cell::Cell;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Once;
+use std::{env, fs};
+
+use super::{generator, Generator, Rng};
+use crate::template::config;
+use crate::Day;
+
+const DEFAULT_CASES: u64 = 64;
+const MAX_SIZE: usize = 8;
+/// Number of seeds tried per size when looking for a smaller failing input.
+const SHRINK_SEEDS: u64 = 32;
+/// Reported in place of the answer if the solution panicked.
+const PANIC: &str = "a panic";
+
+/// A solution with its answer converted to a string.
+type Solution<'a> = Box<dyn Fn(&str) -> Option<String> + 'a>;
+
+struct Part<'a> {
+    part: u8,
+    solution: Solution<'a>,
+    reference: fn(&str) -> String,
+}
+
+enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// The reference rejected the input, so there is nothing to compare against.
+    Invalid,
+}
+
+/// Checks both parts of a solution against the reference solvers of `day`.
+///
+/// Replays all regressions of the day first, then runs `AOC_CHECK_CASES` (default: 64) generated
+/// inputs, starting from seed `AOC_CHECK_SEED` (default: 0).
+///
+/// # Panics
+/// Panics on the first mismatch, or if `day` has no generator.
+pub fn check<A: Display, B: Display>(
+    day: Day,
+    part_one: impl Fn(&str) -> Option<A>,
+    part_two: impl Fn(&str) -> Option<B>,
+) {
+    let generator = generator(day).unwrap_or_else(|| panic!("day {day} has no generator"));
+    let parts = [
+        Part {
+            part: 1,
+            solution: Box::new(|input| part_one(input).map(|r| r.to_string())),
+            reference: generator.part_one,
+        },
+        Part {
+            part: 2,
+            solution: Box::new(|input| part_two(input).map(|r| r.to_string())),
+            reference: generator.part_two,
+        },
+    ];
+
+    for part in &parts {
+        for (path, input) in regressions(day, part.part) {
+            if let Outcome::Fail { expected, actual } = run(part, &input) {
+                panic!("regression \"{path}\" failed: expected {expected}, got {actual}.");
+            }
+        }
+    }
+
+    let cases = env_var("AOC_CHECK_CASES").unwrap_or(DEFAULT_CASES);
+    let base_seed = env_var("AOC_CHECK_SEED").unwrap_or(0);
+
+    for i in 0..cases {
+        let seed = base_seed.wrapping_add(i);
+        let size = 1 + (i as usize % MAX_SIZE);
+        let input = (generator.input)(&mut Rng::new(seed), size);
+
+        for part in &parts {
+            if let Outcome::Fail { actual, .. } = silenced(|| run(part, &input)) {
+                let panicked = actual == PANIC;
+                let input = silenced(|| shrink(&generator, part, input.clone(), size, panicked));
+                let Outcome::Fail { expected, actual } = run(part, &input) else {
+                    unreachable!("shrinking only keeps failing inputs");
+                };
+                let path = write_regression(day, part.part, &input);
+                panic!(
+                    "day {day} part {} differs from the reference for seed {seed}, size {size}: \
+                     expected {expected}, got {actual}. The shrunk input was written to \"{path}\".",
+                    part.part
+                );
+            }
+        }
+    }
+}
+
+fn env_var(name: &str) -> Option<u64> {
+    env::var(name).ok()?.parse().ok()
+}
+
+fn run(part: &Part, input: &str) -> Outcome {
+    let reference = part.reference;
+    let Ok(expected) = panic::catch_unwind(|| reference(input)) else {
+        return Outcome::Invalid;
+    };
+
+    let actual = panic::catch_unwind(AssertUnwindSafe(|| (part.solution)(input))).map_or_else(
+        |_| PANIC.to_string(),
+        |r| r.unwrap_or_else(|| "None".to_string()),
+    );
+
+    if expected == actual {
+        Outcome::Pass
+    } else {
+        Outcome::Fail { expected, actual }
+    }
+}
+
+thread_local! {
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` without printing the messages of panics caught inside of it.
+///
+/// The panic hook is process-global, so it is replaced only once, by a hook that skips panics of
+/// threads that are inside `silenced` and forwards all others to the previous hook. Tests that run
+/// in parallel threads keep their panic messages.
+pub(super) fn silenced<T>(f: impl FnOnce() -> T) -> T {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    /// Restores the previous state when `f` returns or unwinds, so `silenced` can be nested.
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            SILENCED.with(|silenced| silenced.set(self.0));
+        }
+    }
+
+    let _reset = Reset(SILENCED.with(|silenced| silenced.replace(true)));
+    f()
+}
+
+/// Whether `input` fails the same way as the original input, so shrinking doesn't turn a wrong
+/// answer into an input that the solution rightly rejects.
+fn fails(part: &Part, input: &str, panicked: bool) -> bool {
+    matches!(run(part, input), Outcome::Fail { actual, .. } if (actual == PANIC) == panicked)
+}
+
+/// Looks for a smaller failing input: first by generating inputs of smaller sizes, then by
+/// removing single lines as long as the input keeps failing.
+fn shrink(
+    generator: &Generator,
+    part: &Part,
+    input: String,
+    size: usize,
+    panicked: bool,
+) -> String {
+    let mut input = (1..size)
+        .flat_map(|size| (0..SHRINK_SEEDS).map(move |seed| (seed, size)))
+        .map(|(seed, size)| (generator.input)(&mut Rng::new(seed), size))
+        .find(|candidate| fails(part, candidate, panicked))
+        .unwrap_or(input);
+
+    if !generator.independent_lines {
+        return input;
+    }
+
+    let trailing_newline = if input.ends_with('\n') { "\n" } else { "" };
+    let mut lines: Vec<String> = input.lines().map(String::from).collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let mut candidate = lines.clone();
+        candidate.remove(i);
+        let joined = candidate.join("\n") + trailing_newline;
+        if fails(part, &joined, panicked) {
+            lines = candidate;
+            input = joined;
+        } else {
+            i += 1;
+        }
+    }
+
+    input
+}
+
+/// Returns `(path, input)` for all regressions of a day and part.
+fn regressions(day: Day, part: u8) -> Vec<(String, String)> {
+    let prefix = format!("{day}-{part}-");
+    let Ok(entries) = fs::read_dir(regressions_dir()) else {
+        return vec![];
+    };
+
+    let mut res: Vec<(String, String)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .map(|path| {
+            let input = fs::read_to_string(&path).expect("could not read regression file");
+            (path.display().to_string(), input)
+        })
+        .collect();
+    res.sort();
+    res
+}
+
+fn write_regression(day: Day, part: u8, input: &str) -> String {
+    // FNV-1a, so the same failing input always maps to the same file.
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let dir = regressions_dir();
+    let path = dir.join(format!("{day}-{part}-{:08x}.txt", hash as u32));
+    fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&path, input))
+        .expect("could not write regression file");
+    path.display().to_string()
+}
+
+fn regressions_dir() -> PathBuf {
+    config::get().data.folder("regressions")
+}
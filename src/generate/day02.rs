@@ -0,0 +1,56 @@
+/// Day 02: games of colored cubes drawn from a bag.
+use super::Rng;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+const LIMITS: [u32; 3] = [12, 13, 14];
+
+/// Generates `size` games with up to six rounds each. Counts go slightly above the part one
+/// limits, so roughly half of the games are impossible.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(1))
+        .map(|id| {
+            let rounds: Vec<String> = (0..rng.range(1..=6))
+                .map(|_| {
+                    let mut colors = COLORS.to_vec();
+                    rng.shuffle(&mut colors);
+                    colors.truncate(rng.range(1..=3) as usize);
+                    colors
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.range(1..=16)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {id}: {}\n", rounds.join("; "))
+        })
+        .collect()
+}
+
+/// Returns the highest count seen for each color of a game, and the game id.
+fn max_counts(line: &str) -> (u32, [u32; 3]) {
+    let (game, rounds) = line.split_once(": ").unwrap();
+    let id = game.trim_start_matches("Game ").parse().unwrap();
+    let mut max = [0; 3];
+    for cubes in rounds.split([';', ',']) {
+        let (count, color) = cubes.trim().split_once(' ').unwrap();
+        let index = COLORS.iter().position(|&c| c == color).unwrap();
+        max[index] = max[index].max(count.parse().unwrap());
+    }
+    (id, max)
+}
+
+pub fn part_one(input: &str) -> u32 {
+    input
+        .lines()
+        .map(max_counts)
+        .filter(|(_, max)| max.iter().zip(LIMITS).all(|(&m, limit)| m <= limit))
+        .map(|(id, _)| id)
+        .sum()
+}
+
+pub fn part_two(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| max_counts(line).1.iter().product::<u32>())
+        .sum()
+}
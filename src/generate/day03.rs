@@ -0,0 +1,119 @@
+/// Day 03: part numbers in an engine schematic.
+use super::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/=@%&-";
+
+/// Generates a `size` x `size + 3` grid with numbers and symbols. Numbers are placed anywhere,
+/// including at the line edges, and may touch each other.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let height = size.max(2);
+    let width = height + 3;
+    let mut grid = vec![vec![b'.'; width]; height];
+
+    for row in &mut grid {
+        for _ in 0..width / 4 {
+            let len = rng.range(1..=3) as usize;
+            let col = rng.below((width - len + 1) as u64) as usize;
+            for cell in &mut row[col..col + len] {
+                *cell = b'0' + rng.range(1..=9) as u8;
+            }
+        }
+        for _ in 0..width / 6 {
+            let col = rng.below(width as u64) as usize;
+            row[col] = if rng.one_in(2) {
+                b'*'
+            } else {
+                *rng.choose(SYMBOLS)
+            };
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+/// A number in the grid: its value, row and column range.
+struct Number {
+    value: u32,
+    row: usize,
+    cols: std::ops::Range<usize>,
+}
+
+fn numbers(grid: &[&[u8]]) -> Vec<Number> {
+    let mut res = vec![];
+    for (row, line) in grid.iter().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if line[col].is_ascii_digit() {
+                let start = col;
+                while col < line.len() && line[col].is_ascii_digit() {
+                    col += 1;
+                }
+                let value = std::str::from_utf8(&line[start..col])
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                res.push(Number {
+                    value,
+                    row,
+                    cols: start..col,
+                });
+            } else {
+                col += 1;
+            }
+        }
+    }
+    res
+}
+
+/// Checks every one of the 8 neighbors of every digit of `number`.
+fn is_adjacent(number: &Number, row: usize, col: usize) -> bool {
+    number.row.abs_diff(row) <= 1 && number.cols.clone().any(|c| c.abs_diff(col) <= 1)
+}
+
+fn symbols(grid: &[&[u8]]) -> Vec<(usize, usize, u8)> {
+    let mut res = vec![];
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            if cell != b'.' && !cell.is_ascii_digit() {
+                res.push((row, col, cell));
+            }
+        }
+    }
+    res
+}
+
+pub fn part_one(input: &str) -> u32 {
+    let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let symbols = symbols(&grid);
+    numbers(&grid)
+        .iter()
+        .filter(|n| {
+            symbols
+                .iter()
+                .any(|&(row, col, _)| is_adjacent(n, row, col))
+        })
+        .map(|n| n.value)
+        .sum()
+}
+
+pub fn part_two(input: &str) -> u32 {
+    let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let numbers = numbers(&grid);
+    symbols(&grid)
+        .iter()
+        .filter(|&&(_, _, cell)| cell == b'*')
+        .map(|&(row, col, _)| {
+            let adjacent: Vec<u32> = numbers
+                .iter()
+                .filter(|n| is_adjacent(n, row, col))
+                .map(|n| n.value)
+                .collect();
+            match adjacent[..] {
+                [a, b] => a * b,
+                _ => 0,
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,57 @@
+/// Day 04: scratchcards.
+use super::Rng;
+
+/// Generates `size` cards with 5 winning and 8 owned numbers each.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut pool: Vec<u64> = (1..=40).collect();
+    (1..=size.max(1))
+        .map(|id| {
+            rng.shuffle(&mut pool);
+            let winning = &pool[..5];
+            // draw owned numbers from an overlapping window so cards win 0 to 5 times. Most cards
+            // don't win at all, otherwise the number of copies in part two grows exponentially.
+            let offset = rng.range(0..=15) as usize;
+            let mut owned = pool[offset..offset + 8].to_vec();
+            rng.shuffle(&mut owned);
+            let format = |nums: &[u64]| {
+                nums.iter()
+                    .map(|n| format!("{n:>2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            format!("Card {id:>3}: {} | {}\n", format(winning), format(&owned))
+        })
+        .collect()
+}
+
+fn matches(line: &str) -> usize {
+    let (_, numbers) = line.split_once(':').unwrap();
+    let (winning, owned) = numbers.split_once('|').unwrap();
+    let winning: Vec<&str> = winning.split_whitespace().collect();
+    owned
+        .split_whitespace()
+        .filter(|n| winning.contains(n))
+        .count()
+}
+
+pub fn part_one(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| match matches(line) {
+            0 => 0,
+            n => 1 << (n - 1),
+        })
+        .sum()
+}
+
+/// Processes every card instance one by one, including all the won copies.
+pub fn part_two(input: &str) -> u32 {
+    let matches: Vec<usize> = input.lines().map(matches).collect();
+    let mut pending: Vec<usize> = (0..matches.len()).collect();
+    let mut total = 0;
+    while let Some(card) = pending.pop() {
+        total += 1;
+        pending.extend(card + 1..(card + 1 + matches[card]).min(matches.len()));
+    }
+    total
+}
@@ -0,0 +1,109 @@
+/// Day 05: seed almanac with layers of range maps.
+use super::Rng;
+
+const LAYERS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Generates seeds and maps over the values `0..size * 10`. Each layer partitions that domain
+/// into touching source ranges, some of which are left unmapped, and seed ranges often start or
+/// end right at a partition boundary.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let domain = (size.max(1) * 10) as u64;
+    let mut boundaries = vec![0, domain];
+    let mut sections = vec![];
+
+    for name in LAYERS {
+        let mut cuts: Vec<u64> = (0..rng.range(1..=6)).map(|_| rng.below(domain)).collect();
+        cuts.extend([0, domain]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        boundaries.extend(&cuts);
+
+        let mut maps: Vec<String> = cuts
+            .windows(2)
+            .filter_map(|w| {
+                let dest = rng.below(domain);
+                (!rng.one_in(3)).then(|| format!("{dest} {} {}", w[0], w[1] - w[0]))
+            })
+            .collect();
+        rng.shuffle(&mut maps);
+        sections.push(format!("{name} map:\n{}", maps.join("\n")));
+    }
+
+    let seeds: Vec<String> = (0..4)
+        .map(|_| {
+            let start = if rng.one_in(2) {
+                *rng.choose(&boundaries) % domain
+            } else {
+                rng.below(domain)
+            };
+            let end = if rng.one_in(2) {
+                *rng.choose(&boundaries)
+            } else {
+                rng.range(start + 1..=domain)
+            };
+            let len = end.saturating_sub(start).max(1);
+            format!("{start} {len}")
+        })
+        .collect();
+
+    format!("seeds: {}\n\n{}\n", seeds.join(" "), sections.join("\n\n"))
+}
+
+type Layer = Vec<[u64; 3]>;
+
+fn parse(input: &str) -> (Vec<u64>, Vec<Layer>) {
+    let mut sections = input.trim().split("\n\n");
+    let seeds = sections.next().unwrap()["seeds:".len()..]
+        .split_whitespace()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    let layers = sections
+        .map(|section| {
+            section
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let nums: Vec<u64> = line
+                        .split_whitespace()
+                        .map(|s| s.parse().unwrap())
+                        .collect();
+                    [nums[0], nums[1], nums[2]]
+                })
+                .collect()
+        })
+        .collect();
+    (seeds, layers)
+}
+
+fn convert(layers: &[Layer], seed: u64) -> u64 {
+    layers.iter().fold(seed, |value, layer| {
+        layer
+            .iter()
+            .find(|[_, src, len]| (*src..src + len).contains(&value))
+            .map_or(value, |[dest, src, _]| dest + value - src)
+    })
+}
+
+pub fn part_one(input: &str) -> u32 {
+    let (seeds, layers) = parse(input);
+    seeds.iter().map(|&s| convert(&layers, s)).min().unwrap() as u32
+}
+
+/// Converts every single seed of every range.
+pub fn part_two(input: &str) -> usize {
+    let (seeds, layers) = parse(input);
+    seeds
+        .chunks(2)
+        .flat_map(|pair| pair[0]..pair[0] + pair[1])
+        .map(|s| convert(&layers, s))
+        .min()
+        .unwrap() as usize
+}
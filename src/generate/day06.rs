@@ -0,0 +1,55 @@
+/// Day 06: boat races.
+use super::Rng;
+
+/// Generates `size` races (at most 3, so part two stays brute-forceable) with times up to 60.
+/// Some records are unbeatable, which makes the answer zero.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let races: Vec<(u64, u64)> = (0..size.clamp(1, 3))
+        .map(|_| {
+            let time = rng.range(1..=60);
+            (time, rng.range(0..=time * time / 4 + 2))
+        })
+        .collect();
+
+    let width = races
+        .iter()
+        .map(|(t, d)| t.to_string().len().max(d.to_string().len()))
+        .collect::<Vec<_>>();
+    let row = |label: &str, values: Vec<u64>| {
+        let cells: Vec<String> = values
+            .iter()
+            .zip(&width)
+            .map(|(v, w)| format!("{v:>w$}"))
+            .collect();
+        format!("{label:<9} {}\n", cells.join("  "))
+    };
+
+    row("Time:", races.iter().map(|r| r.0).collect())
+        + &row("Distance:", races.iter().map(|r| r.1).collect())
+}
+
+fn wins(time: u64, distance: u64) -> u64 {
+    (0..=time).filter(|x| x * (time - x) > distance).count() as u64
+}
+
+fn numbers(line: &str) -> Vec<&str> {
+    line.split_whitespace().skip(1).collect()
+}
+
+pub fn part_one(input: &str) -> u64 {
+    let mut lines = input.lines();
+    let times = numbers(lines.next().unwrap());
+    let distances = numbers(lines.next().unwrap());
+    times
+        .iter()
+        .zip(distances)
+        .map(|(t, d)| wins(t.parse().unwrap(), d.parse().unwrap()))
+        .product()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    let mut lines = input.lines();
+    let time = numbers(lines.next().unwrap()).concat();
+    let distance = numbers(lines.next().unwrap()).concat();
+    wins(time.parse().unwrap(), distance.parse().unwrap())
+}
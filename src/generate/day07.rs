@@ -0,0 +1,104 @@
+/// Day 07: camel cards.
+use std::collections::HashSet;
+
+use super::Rng;
+
+const LABELS: &[u8] = b"23456789TJQKA";
+const LABELS_WITH_JOKER: &[u8] = b"J23456789TQKA";
+
+/// Generates `size` distinct hands. Some hands are biased towards jokers, including `JJJJJ`.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut lines = vec![];
+
+    while lines.len() < size.max(1) {
+        let hand: String = match rng.below(10) {
+            0 => "JJJJJ".into(),
+            1 | 2 => (0..5)
+                .map(|_| {
+                    if rng.one_in(2) {
+                        'J'
+                    } else {
+                        *rng.choose(LABELS) as char
+                    }
+                })
+                .collect(),
+            _ => (0..5).map(|_| *rng.choose(LABELS) as char).collect(),
+        };
+        if seen.insert(hand.clone()) {
+            lines.push(format!("{hand} {}\n", rng.range(1..=1000)));
+        }
+
+        // every hand is already in the input, stop before looping forever.
+        if seen.len() == LABELS.len().pow(5) {
+            break;
+        }
+    }
+
+    lines.concat()
+}
+
+/// Ranks the hand type from high card (0) to five of a kind (6).
+fn hand_type(cards: &[u8]) -> u8 {
+    let mut counts: Vec<usize> = LABELS
+        .iter()
+        .map(|l| cards.iter().filter(|c| *c == l).count())
+        .filter(|&c| c > 0)
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5] => 6,
+        [4, 1] => 5,
+        [3, 2] => 4,
+        [3, 1, 1] => 3,
+        [2, 2, 1] => 2,
+        [2, 1, 1, 1] => 1,
+        _ => 0,
+    }
+}
+
+fn winnings(input: &str, key: impl Fn(&[u8]) -> (u8, Vec<usize>)) -> u32 {
+    let mut hands: Vec<((u8, Vec<usize>), u32)> = input
+        .lines()
+        .map(|line| {
+            let (cards, bid) = line.split_once(' ').unwrap();
+            (key(cards.as_bytes()), bid.trim().parse().unwrap())
+        })
+        .collect();
+    hands.sort_by(|a, b| a.0.cmp(&b.0));
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bid))| (i as u32 + 1) * bid)
+        .sum()
+}
+
+fn strengths(cards: &[u8], labels: &[u8]) -> Vec<usize> {
+    cards
+        .iter()
+        .map(|c| labels.iter().position(|l| l == c).unwrap())
+        .collect()
+}
+
+pub fn part_one(input: &str) -> u32 {
+    winnings(input, |cards| (hand_type(cards), strengths(cards, LABELS)))
+}
+
+/// Tries every label as the replacement for all jokers.
+pub fn part_two(input: &str) -> u32 {
+    winnings(input, |cards| {
+        let best = LABELS
+            .iter()
+            .filter(|&&l| l != b'J')
+            .map(|&l| {
+                let replaced: Vec<u8> = cards
+                    .iter()
+                    .map(|&c| if c == b'J' { l } else { c })
+                    .collect();
+                hand_type(&replaced)
+            })
+            .max()
+            .unwrap();
+        (best, strengths(cards, LABELS_WITH_JOKER))
+    })
+}
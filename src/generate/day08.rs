@@ -0,0 +1,125 @@
+/// Day 08: haunted wasteland network.
+use std::collections::{HashMap, HashSet};
+
+use super::Rng;
+
+/// Generates a network for `size` ghosts (at most 5). Every ghost walks a cycle from its `..A`
+/// node to its `..Z` node, and the `..Z` node loops back to the first node after the start, like
+/// the real inputs do. Ghost 0 starts at `AAA` and ends at `ZZZ`. Unreachable decoy nodes are
+/// mixed in.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    let ghosts = size.clamp(1, 5);
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut label = |rng: &mut Rng, last: Option<u8>| unique_label(rng, &mut used, last);
+
+    let mut entries = vec![];
+    for ghost in 0..ghosts {
+        let (start, goal) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (label(rng, Some(b'A')), label(rng, Some(b'Z')))
+        };
+
+        let len = rng.range(1..=ghosts as u64 + 6) as usize;
+        let mut chain = vec![start];
+        chain.extend((1..len).map(|_| label(rng, None)));
+        chain.push(goal.clone());
+
+        for w in chain.windows(2) {
+            entries.push((w[0].clone(), w[1].clone(), w[1].clone()));
+        }
+        entries.push((goal, chain[1].clone(), chain[1].clone()));
+    }
+
+    let decoys: Vec<String> = (0..rng.range(1..=5)).map(|_| label(rng, None)).collect();
+    for decoy in &decoys {
+        entries.push((
+            decoy.clone(),
+            rng.choose(&decoys).clone(),
+            rng.choose(&decoys).clone(),
+        ));
+    }
+    rng.shuffle(&mut entries);
+
+    let instructions: String = (0..rng.range(1..=5))
+        .map(|_| if rng.one_in(2) { 'L' } else { 'R' })
+        .collect();
+    let network: String = entries
+        .iter()
+        .map(|(node, left, right)| format!("{node} = ({left}, {right})\n"))
+        .collect();
+
+    format!("{instructions}\n\n{network}")
+}
+
+/// Returns a new label made of `B..=Y`, optionally ending in `last`.
+fn unique_label(rng: &mut Rng, used: &mut HashSet<String>, last: Option<u8>) -> String {
+    loop {
+        let mut label: Vec<u8> = (0..3).map(|_| b'B' + rng.below(24) as u8).collect();
+        if let Some(last) = last {
+            label[2] = last;
+        }
+        let label = String::from_utf8(label).unwrap();
+        if used.insert(label.clone()) {
+            return label;
+        }
+    }
+}
+
+fn parse(input: &str) -> (&str, HashMap<&str, (&str, &str)>) {
+    let (instructions, network) = input.split_once('\n').unwrap();
+    let network = network
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let (node, edges) = line.split_once(" = ").unwrap();
+            let (left, right) = edges.trim_matches(['(', ')']).split_once(", ").unwrap();
+            (node, (left, right))
+        })
+        .collect();
+    (instructions.trim(), network)
+}
+
+fn step<'a>(network: &HashMap<&'a str, (&'a str, &'a str)>, node: &str, dir: char) -> &'a str {
+    let (left, right) = network[node];
+    if dir == 'L' {
+        left
+    } else {
+        right
+    }
+}
+
+pub fn part_one(input: &str) -> u64 {
+    let (instructions, network) = parse(input);
+    let mut node = "AAA";
+    let mut steps = 0;
+    for dir in instructions.chars().cycle() {
+        node = step(&network, node, dir);
+        steps += 1;
+        if node == "ZZZ" {
+            break;
+        }
+    }
+    steps
+}
+
+/// Walks all ghosts at the same time until they are all on a `..Z` node.
+pub fn part_two(input: &str) -> u64 {
+    let (instructions, network) = parse(input);
+    let mut nodes: Vec<&str> = network
+        .keys()
+        .copied()
+        .filter(|n| n.ends_with('A'))
+        .collect();
+    let mut steps = 0;
+    for dir in instructions.chars().cycle() {
+        for node in &mut nodes {
+            *node = step(&network, node, dir);
+        }
+        steps += 1;
+        if nodes.iter().all(|n| n.ends_with('Z')) {
+            break;
+        }
+    }
+    steps
+}
@@ -0,0 +1,56 @@
+/// Day 09: sequence extrapolation.
+use super::Rng;
+
+/// Generates `size` sequences of 6 to 12 values from random polynomials of degree 0 to 4.
+pub fn input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let degree = rng.range(0..=4) as usize;
+            let coefficients: Vec<i64> = (0..=degree).map(|_| rng.range_i64(-6..=6)).collect();
+            let len = rng.range(degree as u64 + 2..=12).max(6);
+            let values: Vec<String> = (0..len as i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, c| acc * x + c)
+                        .to_string()
+                })
+                .collect();
+            values.join(" ") + "\n"
+        })
+        .collect()
+}
+
+fn parse(line: &str) -> Vec<i64> {
+    line.split_whitespace()
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+/// Builds the full table of differences and walks it back up.
+fn next(values: &[i64]) -> i64 {
+    let mut rows = vec![values.to_vec()];
+    while rows.last().unwrap().iter().any(|&v| v != 0) {
+        let last = rows.last().unwrap();
+        rows.push(last.windows(2).map(|w| w[1] - w[0]).collect());
+    }
+    rows.iter()
+        .map(|row| row.last().copied().unwrap_or(0))
+        .sum()
+}
+
+pub fn part_one(input: &str) -> i64 {
+    input.lines().map(|line| next(&parse(line))).sum()
+}
+
+pub fn part_two(input: &str) -> i64 {
+    input
+        .lines()
+        .map(|line| {
+            let mut values = parse(line);
+            values.reverse();
+            next(&values)
+        })
+        .sum()
+}
@@ -0,0 +1,139 @@
+/// Mutation-based fuzzing of a day's `parse` entry point that runs on stable Rust.
+///
+/// [`fuzz`] starts from the day's examples and generated inputs, applies random edits and fails
+/// on the first input that makes `parse` panic instead of returning a [`ParseError`]. The
+/// libFuzzer targets in `fuzz/` exercise the same entry points with coverage feedback.
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use super::check::silenced;
+use super::{generator, Rng};
+use crate::parse::{self, ParseError};
+use crate::template::config;
+use crate::Day;
+
+const DEFAULT_ITERATIONS: u64 = 500;
+
+/// Snippets that commonly trip up parsers: separators, numbers that overflow, non-ASCII.
+const INTERESTING: &[&str] = &[
+    " ",
+    "\n",
+    "\n\n",
+    "\t",
+    ":",
+    "|",
+    ",",
+    ";",
+    "=",
+    "(",
+    ")",
+    "-",
+    "0",
+    "-1",
+    "18446744073709551616",
+    "é",
+];
+
+/// Runs `parse` against mutated inputs of `day`. Pass the day's entry point as
+/// `|input| parse(input).map(drop)`, since its output may borrow from the input.
+///
+/// Runs `AOC_FUZZ_ITERATIONS` (default: 500) inputs, or as many as fit into `AOC_FUZZ_SECONDS`
+/// if that is set. Mutations are seeded with `AOC_FUZZ_SEED` (default: 0).
+///
+/// # Panics
+/// Panics with the offending input if `parse` panics, or if it reports an error on a line that
+/// doesn't exist.
+pub fn fuzz(day: Day, parse: impl Fn(&str) -> parse::Result<()>) {
+    let corpus = corpus(day);
+    let seed = env_var("AOC_FUZZ_SEED").unwrap_or(0);
+    let deadline = env_var("AOC_FUZZ_SECONDS").map(|s| Instant::now() + Duration::from_secs(s));
+    let iterations = env_var("AOC_FUZZ_ITERATIONS").unwrap_or(DEFAULT_ITERATIONS);
+
+    let mut rng = Rng::new(seed);
+    let mut i = 0;
+    while deadline.map_or(i < iterations, |deadline| Instant::now() < deadline) {
+        let original = rng.choose(&corpus);
+        let input = mutate(&mut rng, original);
+        let result = silenced(|| panic::catch_unwind(AssertUnwindSafe(|| parse(&input).err())));
+
+        match result {
+            Err(_) => {
+                panic!("day {day} parser panicked on iteration {i} (seed {seed}), input: {input:?}")
+            }
+            Ok(Some(ParseError { line, .. })) if line > input.matches('\n').count() + 1 => {
+                panic!("day {day} parser reported an error on line {line}, input: {input:?}")
+            }
+            Ok(_) => {}
+        }
+        i += 1;
+    }
+}
+
+fn env_var(name: &str) -> Option<u64> {
+    env::var(name).ok()?.parse().ok()
+}
+
+/// The day's examples and regressions, plus a few small generated inputs.
+fn corpus(day: Day) -> Vec<String> {
+    let data = &config::get().data;
+    let mut corpus: Vec<String> = [data.folder("examples"), data.folder("regressions")]
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&day.to_string()) && name.ends_with(".txt"))
+        })
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+
+    if let Some(generator) = generator(day) {
+        for size in 1..=3 {
+            corpus.push((generator.input)(&mut Rng::new(size as u64), size));
+        }
+    }
+    if corpus.is_empty() {
+        corpus.push(String::new());
+    }
+    corpus
+}
+
+/// Applies one to four random edits to `input`.
+fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+
+    for _ in 0..=rng.below(4) {
+        let len = chars.len();
+        let at = rng.below(len as u64 + 1) as usize;
+        let end = (at + 1 + rng.below(8) as usize).min(len);
+
+        match rng.below(5) {
+            0 => {
+                chars.drain(at..end);
+            }
+            1 => {
+                let snippet = rng.choose(INTERESTING).chars();
+                chars.splice(at..at, snippet);
+            }
+            2 if at < len => {
+                chars[at] = if rng.one_in(2) {
+                    *rng.choose(&chars)
+                } else {
+                    rng.choose(INTERESTING).chars().next().unwrap()
+                };
+            }
+            3 => {
+                let from = rng.below(len as u64 + 1) as usize;
+                let copy: Vec<char> = chars[from..(from + 16).min(len)].to_vec();
+                chars.splice(at..at, copy);
+            }
+            _ => chars.truncate(at),
+        }
+    }
+
+    chars.into_iter().collect()
+}
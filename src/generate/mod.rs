@@ -0,0 +1,158 @@
+/// Random puzzle inputs with reference answers.
+///
+/// Every solved day has a generator that writes a valid random input for a given size, and slow
+/// but obviously correct reference solvers. They are used by `cargo gen` and to check optimized
+/// solutions against inputs that the single worked example doesn't cover.
+use crate::Day;
+
+mod check;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+mod fuzz;
+mod rng;
+
+pub use check::check;
+pub use fuzz::fuzz;
+pub use rng::Rng;
+
+/// Input generator and reference solvers for one day.
+pub struct Generator {
+    /// What the `size` argument controls for this day.
+    pub size_hint: &'static str,
+    /// Whether removing lines from a valid input keeps it valid, which allows [`check`] to shrink
+    /// failing inputs line by line.
+    pub independent_lines: bool,
+    pub input: fn(&mut Rng, usize) -> String,
+    pub part_one: fn(&str) -> String,
+    pub part_two: fn(&str) -> String,
+}
+
+/// A generated input together with its reference answers.
+pub struct Generated {
+    pub input: String,
+    pub part_one: String,
+    pub part_two: String,
+}
+
+impl Generator {
+    #[must_use]
+    pub fn generate(&self, seed: u64, size: usize) -> Generated {
+        let input = (self.input)(&mut Rng::new(seed), size);
+        Generated {
+            part_one: (self.part_one)(&input),
+            part_two: (self.part_two)(&input),
+            input,
+        }
+    }
+}
+
+macro_rules! generator {
+    ($day:ident, $size_hint:expr, $independent_lines:expr) => {
+        Generator {
+            size_hint: $size_hint,
+            independent_lines: $independent_lines,
+            input: $day::input,
+            part_one: |input| $day::part_one(input).to_string(),
+            part_two: |input| $day::part_two(input).to_string(),
+        }
+    };
+}
+
+/// Returns the generator for `day`, if that day has one.
+#[must_use]
+pub fn generator(day: Day) -> Option<Generator> {
+    let generator = match day.into_inner() {
+        2 => generator!(day02, "number of games", true),
+        3 => generator!(day03, "grid height", true),
+        4 => generator!(day04, "number of cards", false),
+        5 => generator!(day05, "values per map layer", true),
+        6 => generator!(day06, "number of races (at most 3)", false),
+        7 => generator!(day07, "number of hands", true),
+        8 => generator!(day08, "number of ghosts (at most 5)", true),
+        9 => generator!(day09, "number of sequences", true),
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fuzz, generator, Rng};
+    use crate::{all_days, day, template::read_file};
+
+    #[test]
+    fn rng_is_deterministic() {
+        let a: Vec<_> = (0..5)
+            .map({
+                let mut rng = Rng::new(7);
+                move |_| rng.next_u64()
+            })
+            .collect();
+        let b: Vec<_> = (0..5)
+            .map({
+                let mut rng = Rng::new(7);
+                move |_| rng.next_u64()
+            })
+            .collect();
+        assert_eq!(a, b);
+
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((3..=9).contains(&rng.range(3..=9)));
+            assert!((-4..=2).contains(&rng.range_i64(-4..=2)));
+        }
+    }
+
+    #[test]
+    fn references_solve_examples() {
+        let expected = [
+            (day!(2), "8", "2286"),
+            (day!(3), "4361", "467835"),
+            (day!(4), "13", "30"),
+            (day!(5), "35", "46"),
+            (day!(6), "288", "71503"),
+            (day!(7), "6440", "5905"),
+            (day!(9), "114", "2"),
+        ];
+        for (day, part_one, part_two) in expected {
+            let generator = generator(day).unwrap();
+            let input = read_file("examples", day);
+            assert_eq!((generator.part_one)(&input), part_one, "day {day}");
+            assert_eq!((generator.part_two)(&input), part_two, "day {day}");
+        }
+
+        let generator = generator(day!(8)).unwrap();
+        assert_eq!((generator.part_two)(&read_file("examples", day!(8))), "6");
+    }
+
+    #[test]
+    fn generates_reproducible_inputs() {
+        for day in all_days() {
+            let Some(generator) = generator(day) else {
+                continue;
+            };
+            let a = generator.generate(42, 5);
+            let b = generator.generate(42, 5);
+            assert_eq!(a.input, b.input, "day {day}");
+            assert_eq!(a.part_one, b.part_one, "day {day}");
+            assert_eq!(a.part_two, b.part_two, "day {day}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "day 02 parser panicked")]
+    fn fuzz_reports_panics() {
+        fuzz(day!(2), |input| {
+            assert!(!input.contains('|'), "unexpected `|`");
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,220 @@
+/// Dense ids for string-keyed puzzle graphs.
+///
+/// Puzzles like day 08 describe graphs with labels such as `AAA = (BBB, CCC)`. Walking those
+/// through a `HashMap<String, _>` hashes (and often clones) a string on every step. The types in
+/// this module map every label to a dense `u32` once, so walks only index into vectors.
+use std::collections::HashMap;
+
+/// Maps string labels (e.g. `AAA` or `11Z`) to dense `u32` ids, in order of first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    ids: HashMap<&'a str, u32>,
+    labels: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id for `label`, assigning the next free id if it has not been seen yet.
+    pub fn intern(&mut self, label: &'a str) -> u32 {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+
+        let id = u32::try_from(self.labels.len()).expect("too many labels to intern");
+        self.ids.insert(label, id);
+        self.labels.push(label);
+        id
+    }
+
+    /// Returns the id of an already interned `label`.
+    #[must_use]
+    pub fn get(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    /// Returns the label for `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was not handed out by this interner.
+    #[must_use]
+    pub fn label(&self, id: u32) -> &'a str {
+        self.labels[id as usize]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Iterates over all `(id, label)` pairs in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &'a str)> + '_ {
+        self.labels.iter().enumerate().map(|(i, &l)| (i as u32, l))
+    }
+
+    /// Evaluates `predicate` for every label once and stores the result as a [`BitSet`].
+    pub fn select(&self, predicate: impl Fn(&str) -> bool) -> BitSet {
+        let mut set = BitSet::with_capacity(self.len());
+        self.iter()
+            .filter(|(_, label)| predicate(label))
+            .for_each(|(id, _)| set.insert(id));
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A fixed-capacity set of ids, stored as one bit per id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// Creates an empty set that can hold ids in range `0..capacity` without reallocating.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+        }
+    }
+
+    pub fn insert(&mut self, id: u32) {
+        let word = id as usize / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (id % 64);
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: u32) -> bool {
+        self.words
+            .get(id as usize / 64)
+            .is_some_and(|w| w & (1 << (id % 64)) != 0)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Iterates over the contained ids in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| (i * 64 + bit) as u32)
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A graph where every node has exactly two outgoing edges, e.g. the `L`/`R` network of day 08.
+#[derive(Debug, Clone, Default)]
+pub struct Network<'a> {
+    pub interner: Interner<'a>,
+    /// Outgoing edges indexed by node id: `[left, right]`.
+    pub edges: Vec<[u32; 2]>,
+}
+
+impl<'a> Network<'a> {
+    /// Builds a network from `(node, [left, right])` entries.
+    /// Nodes that are only ever referenced as targets point to themselves.
+    pub fn from_entries(entries: impl IntoIterator<Item = (&'a str, [&'a str; 2])>) -> Self {
+        let mut interner = Interner::new();
+        let mut edges: Vec<[u32; 2]> = vec![];
+
+        for (node, [left, right]) in entries {
+            let ids = [
+                interner.intern(node),
+                interner.intern(left),
+                interner.intern(right),
+            ];
+            while edges.len() < interner.len() {
+                let id = edges.len() as u32;
+                edges.push([id, id]);
+            }
+            edges[ids[0] as usize] = [ids[1], ids[2]];
+        }
+
+        Self { interner, edges }
+    }
+
+    /// Follows edge `side` (`0` for left, `1` for right) from `node`.
+    #[must_use]
+    #[inline]
+    pub fn step(&self, node: u32, side: usize) -> u32 {
+        self.edges[node as usize][side]
+    }
+
+    /// Shorthand for [`Interner::select`].
+    pub fn select(&self, predicate: impl Fn(&str) -> bool) -> BitSet {
+        self.interner.select(predicate)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitSet, Interner, Network};
+
+    #[test]
+    fn interns_in_order_of_appearance() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("AAA"), 0);
+        assert_eq!(interner.intern("BBB"), 1);
+        assert_eq!(interner.intern("AAA"), 0);
+        assert_eq!(interner.get("BBB"), Some(1));
+        assert_eq!(interner.get("CCC"), None);
+        assert_eq!(interner.label(1), "BBB");
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn bitset_operations() {
+        let mut set = BitSet::with_capacity(10);
+        set.insert(3);
+        set.insert(130);
+        assert!(set.contains(3));
+        assert!(set.contains(130));
+        assert!(!set.contains(4));
+        assert!(!set.contains(1000));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 130]);
+    }
+
+    #[test]
+    fn builds_network() {
+        let network = Network::from_entries([
+            ("11A", ["11B", "XXX"]),
+            ("11B", ["XXX", "11Z"]),
+            ("11Z", ["11B", "XXX"]),
+        ]);
+        let start = network.interner.get("11A").unwrap();
+        let goals = network.select(|l| l.ends_with('Z'));
+
+        let b = network.step(start, 0);
+        assert_eq!(network.interner.label(b), "11B");
+        assert!(goals.contains(network.step(b, 1)));
+
+        // `XXX` is never defined and loops onto itself.
+        let x = network.interner.get("XXX").unwrap();
+        assert_eq!(network.edges[x as usize], [x, x]);
+    }
+}
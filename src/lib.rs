@@ -1,4 +1,14 @@
+pub mod bigint;
+pub mod checked;
 mod day;
+pub mod explain;
+pub mod generate;
+pub mod interner;
+pub mod parse;
+pub mod polynomial;
+pub mod profile;
+pub mod quadratic;
 pub mod template;
+pub mod viz;
 
 pub use day::*;
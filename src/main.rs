@@ -1,92 +1,184 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, completions, config, download, explore, generate, progress, read, report, scaffold,
+    snapshots, solve, today,
+};
+use args::{parse, AppArguments, ConfigCommand};
 
 mod args {
-    use std::process;
+    use std::env;
+    use std::path::PathBuf;
 
+    use advent_of_code::template::commands::solve;
     use advent_of_code::Day;
+    use clap::{CommandFactory, Parser, Subcommand};
+    use clap_complete::Shell;
 
+    /// Scaffold, run and benchmark Advent of Code solutions.
+    ///
+    /// The commands are usually run through their cargo aliases, e.g. `cargo solve 1`.
+    #[derive(Parser)]
+    #[command(version)]
+    struct Cli {
+        #[command(subcommand)]
+        command: AppArguments,
+        /// Read the settings from this file instead of `aoc.toml`.
+        #[arg(long, global = true, value_name = "PATH")]
+        config: Option<PathBuf>,
+        /// The year of the puzzles, overrides `year` in `aoc.toml`.
+        #[arg(long, global = true)]
+        year: Option<u16>,
+        /// The folder of the puzzle data, overrides `data.dir` in `aoc.toml`.
+        #[arg(long, global = true, value_name = "DIR")]
+        data_dir: Option<PathBuf>,
+    }
+
+    #[derive(Subcommand)]
     pub enum AppArguments {
-        Download {
+        /// Run all solutions (`cargo all`, `cargo time`).
+        All {
+            /// Build optimized binaries.
+            #[arg(long)]
+            release: bool,
+            /// Benchmark the solutions, and with `--release` update the readme table.
+            #[arg(long)]
+            time: bool,
+            /// Fail if the readme table is out of date instead of updating it.
+            #[arg(long, requires_all = ["release", "time"])]
+            check: bool,
+        },
+        /// Print a completion script for a shell.
+        Completions {
+            shell: Shell,
+            /// The command to complete, e.g. an alias of the `advent_of_code` binary.
+            #[arg(long, default_value = "advent_of_code")]
+            name: String,
+        },
+        /// Inspect the settings of `aoc.toml` (`cargo settings`).
+        #[command(subcommand)]
+        Config(ConfigCommand),
+        /// Download the input and description of a puzzle with aoc-cli.
+        Download { day: Day },
+        /// Write a graph of a day's input to a Graphviz file.
+        Explore {
             day: Day,
+            #[arg(long, value_name = "PATH")]
+            dot: PathBuf,
         },
-        Read {
+        /// Generate a random input with known answers (`cargo gen`).
+        #[command(name = "gen")]
+        Generate {
             day: Day,
+            /// Seed of the generator, random if not set.
+            #[arg(long)]
+            seed: Option<u64>,
+            /// Size of the input, the unit depends on the day.
+            #[arg(long)]
+            size: Option<usize>,
+        },
+        /// Show the stars of every day from the local answer store.
+        Progress {
+            /// Update the stars table in the readme.
+            #[arg(long)]
+            readme: bool,
+            /// Fail if the stars table in the readme is out of date instead of updating it.
+            #[arg(long)]
+            check: bool,
         },
-        Scaffold {
+        /// Read the downloaded description of a puzzle in the terminal.
+        Read {
             day: Day,
+            /// Only show this part.
+            #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: Option<u8>,
+            /// List the code blocks of the description with their indices instead.
+            #[arg(long)]
+            examples: bool,
         },
+        /// Benchmark all solutions and write an HTML report (`cargo timings`).
+        Report {
+            /// The folder to write `index.html` to.
+            #[arg(long, value_name = "DIR")]
+            html: PathBuf,
+        },
+        /// Create the solution, input and example files of a day.
+        Scaffold { day: Day },
+        /// Run the tests of a day, or of all days, that check snapshots of answers.
+        Snapshots {
+            day: Option<Day>,
+            /// Write the answers to the snapshots instead of comparing them.
+            #[arg(long)]
+            bless: bool,
+        },
+        /// Run the solution of a day.
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
+            #[command(flatten)]
+            options: solve::Options,
         },
-        All {
-            release: bool,
-            time: bool,
+        /// Scaffold, download and open the puzzle released last, during December 1-25.
+        Today {
+            /// Print the link to the puzzle instead of opening it in the browser.
+            #[arg(long)]
+            no_open: bool,
         },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    #[derive(Subcommand)]
+    pub enum ConfigCommand {
+        /// Print the settings in effect and where they come from.
+        Show,
+    }
+
+    /// Parses the arguments, or prints the usage and exits if they are invalid.
+    pub fn parse() -> AppArguments {
+        let cli = Cli::parse();
+        set_overrides(&cli);
+        cli.command
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
+    pub fn command() -> clap::Command {
+        Cli::command()
+    }
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    /// Passes the global flags on as the environment variables that override `aoc.toml`, so the
+    /// day binaries that commands run see them as well.
+    fn set_overrides(cli: &Cli) {
+        if let Some(path) = &cli.config {
+            env::set_var("AOC_CONFIG", path);
+        }
+        if let Some(year) = cli.year {
+            env::set_var("AOC_YEAR", year.to_string());
+        }
+        if let Some(dir) = &cli.data_dir {
+            env::set_var("AOC_DATA_DIR", dir);
         }
-
-        Ok(app_args)
     }
 }
 
 fn main() {
     match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+        AppArguments::All {
+            release,
+            time,
+            check,
+        } => all::handle(release, time, check),
+        AppArguments::Completions { shell, name } => {
+            completions::handle(shell, args::command(), &name);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
+        AppArguments::Config(ConfigCommand::Show) => config::show(),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Explore { day, dot } => explore::handle(day, &dot),
+        AppArguments::Generate { day, seed, size } => generate::handle(day, seed, size),
+        AppArguments::Progress { readme, check } => progress::handle(readme, check),
+        AppArguments::Read {
+            day,
+            part,
+            examples,
+        } => read::handle(day, part, examples),
+        AppArguments::Report { html } => report::handle(&html),
+        AppArguments::Scaffold { day } => scaffold::handle(day),
+        AppArguments::Snapshots { day, bless } => snapshots::handle(day, bless),
+        AppArguments::Solve { day, options } => solve::handle(day, &options),
+        AppArguments::Today { no_open } => today::handle(!no_open),
     };
 }
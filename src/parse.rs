@@ -0,0 +1,505 @@
+/// Zero-copy parsing helpers tailored to puzzle inputs.
+///
+/// All helpers operate on [`Span`]s, which are slices of the original input that remember where
+/// they came from. Positions are only computed when an error is reported, so splitting and
+/// scanning stays as cheap as working on plain `&str`s.
+use std::error::Error;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// An error which can be returned when parsing puzzle input.
+/// Lines and columns are 1-based and refer to the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/* -------------------------------------------------------------------------- */
+
+/// A slice of the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    source: &'a str,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// Creates a span covering the whole `input`.
+    #[must_use]
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            source: input,
+            text: input,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Returns the 1-based `(line, column)` this span starts at.
+    #[must_use]
+    pub fn position(&self) -> (usize, usize) {
+        let before = &self.source[..self.offset()];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        (line, column)
+    }
+
+    /// Creates a [`ParseError`] pointing at the start of this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn offset(&self) -> usize {
+        self.text.as_ptr() as usize - self.source.as_ptr() as usize
+    }
+
+    /// Wraps `part`, which must be a sub-slice of this span's text.
+    fn sub(&self, part: &'a str) -> Self {
+        debug_assert!(
+            self.source.as_ptr() as usize <= part.as_ptr() as usize
+                && part.as_ptr() as usize + part.len()
+                    <= self.source.as_ptr() as usize + self.source.len()
+        );
+        Self {
+            source: self.source,
+            text: part,
+        }
+    }
+
+    /// Returns the part of this span at the byte `range`, e.g. of a regex match in [`Self::as_str`].
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds or does not lie on `char` boundaries.
+    #[must_use]
+    pub fn slice(self, range: Range<usize>) -> Self {
+        self.sub(&self.text[range])
+    }
+
+    /// Removes leading and trailing whitespace.
+    #[must_use]
+    pub fn trim(self) -> Self {
+        self.sub(self.text.trim())
+    }
+
+    /// Iterates over the lines of this span, like [`str::lines`].
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().map(move |l| self.sub(l))
+    }
+
+    /// Iterates over blocks of lines separated by one or more blank lines.
+    pub fn sections(self) -> impl Iterator<Item = Span<'a>> {
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|l| l.text.trim().is_empty()).is_some() {}
+            let first = lines.next()?;
+            let mut last = first;
+            while let Some(line) = lines.next_if(|l| !l.text.trim().is_empty()) {
+                last = line;
+            }
+            let start = first.offset() - self.offset();
+            let end = last.offset() + last.text.len() - self.offset();
+            Some(self.sub(&self.text[start..end]))
+        })
+    }
+
+    /// Iterates over the parts of this span separated by `delimiter`, like [`str::split`].
+    pub fn split(self, delimiter: char) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(delimiter).map(move |s| self.sub(s))
+    }
+
+    /// Iterates over the parts of this span separated by the string `delimiter`.
+    pub fn split_str(self, delimiter: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(delimiter).map(move |s| self.sub(s))
+    }
+
+    /// Splits this span at the first occurrence of `delimiter`.
+    pub fn split_once(self, delimiter: &str) -> Result<(Span<'a>, Span<'a>)> {
+        self.text
+            .split_once(delimiter)
+            .map(|(a, b)| (self.sub(a), self.sub(b)))
+            .ok_or_else(|| self.error(format!("expected `{delimiter}`")))
+    }
+
+    /// Iterates over whitespace-separated tokens.
+    pub fn tokens(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.split_whitespace().map(move |s| self.sub(s))
+    }
+
+    /// Iterates over consecutive tokens of `width` characters. The last token may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    pub fn chunks(self, width: usize) -> impl Iterator<Item = Span<'a>> {
+        assert!(width > 0, "chunk width must not be zero");
+        let mut rest = self.text;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let end = rest
+                .char_indices()
+                .nth(width)
+                .map_or(rest.len(), |(i, _)| i);
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+            Some(self.sub(chunk))
+        })
+    }
+
+    /// Parses a `key label: rest` header, such as `Card 12: ...` or `seeds: ...`.
+    /// Returns the trimmed label (empty for `seeds:`) and the rest of the span.
+    pub fn header(self, key: &str) -> Result<(Span<'a>, Span<'a>)> {
+        let text = self.text.trim_start();
+        let rest = text
+            .strip_prefix(key)
+            .ok_or_else(|| self.sub(text).error(format!("expected `{key}`")))?;
+        let (label, rest) = self.sub(rest).split_once(":")?;
+        Ok((label.trim(), rest))
+    }
+
+    /// Parses the trimmed span as a single value.
+    pub fn number<T: FromStr>(self) -> Result<T>
+    where
+        T::Err: Display,
+    {
+        let span = self.trim();
+        span.text
+            .parse()
+            .map_err(|e| span.error(format!("invalid number `{}`: {e}", span.text)))
+    }
+
+    /// Iterates over all unsigned integers in this span, skipping anything in between.
+    pub fn unsigned<T: FromStr>(self) -> Numbers<'a, T> {
+        Numbers::new(self, false)
+    }
+
+    /// Iterates over all integers in this span, treating a `-` directly in front of a number as
+    /// its sign.
+    pub fn signed<T: FromStr>(self) -> Numbers<'a, T> {
+        Numbers::new(self, true)
+    }
+}
+
+impl<'a> Span<'a> {
+    /// Matches the trimmed span against a pattern of `literals` with a capture between every two
+    /// consecutive literals, e.g. `["Game ", ": ", ""]` for `Game {id}: {rounds}`.
+    ///
+    /// Captures are lazy: each one ends at the first occurrence of the literal that follows it. An
+    /// empty last literal lets the last capture take the rest of the span. Runs of spaces or tabs
+    /// in a literal match one or more spaces or tabs, everything else must match exactly.
+    /// Returns the trimmed captures.
+    ///
+    /// # Panics
+    /// Panics if `literals` is empty.
+    pub fn match_pattern(self, literals: &[&str]) -> Result<Vec<Span<'a>>> {
+        let (first, others) = literals
+            .split_first()
+            .expect("a pattern needs at least one literal");
+
+        let mut rest = self.trim();
+        let end = match_literal(rest.text, first).ok_or_else(|| expected(rest, first))?;
+        rest = rest.sub(&rest.text[end..]);
+
+        let mut captures = Vec::with_capacity(others.len());
+        for literal in others {
+            let (start, end) = if literal.is_empty() {
+                (rest.text.len(), rest.text.len())
+            } else {
+                find_literal(rest.text, literal).ok_or_else(|| expected(rest, literal))?
+            };
+            captures.push(rest.sub(&rest.text[..start]).trim());
+            rest = rest.sub(&rest.text[end..]);
+        }
+
+        if !rest.text.trim().is_empty() {
+            return Err(rest.error("unexpected trailing input"));
+        }
+
+        Ok(captures)
+    }
+}
+
+fn expected(span: Span, literal: &str) -> ParseError {
+    span.error(format!("expected `{}`", literal.escape_debug()))
+}
+
+fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Matches `literal` at the start of `text`, returning the end of the match.
+fn match_literal(text: &str, literal: &str) -> Option<usize> {
+    let mut pos = 0;
+    let mut literal = literal;
+
+    while let Some(c) = literal.chars().next() {
+        if is_blank(c) {
+            literal = literal.trim_start_matches(is_blank);
+            let rest = &text[pos..];
+            let skipped = rest.len() - rest.trim_start_matches(is_blank).len();
+            if skipped == 0 {
+                return None;
+            }
+            pos += skipped;
+        } else {
+            if !text[pos..].starts_with(c) {
+                return None;
+            }
+            pos += c.len_utf8();
+            literal = &literal[c.len_utf8()..];
+        }
+    }
+
+    Some(pos)
+}
+
+/// Finds the first match of `literal` in `text`, returning its start and end.
+fn find_literal(text: &str, literal: &str) -> Option<(usize, usize)> {
+    text.char_indices()
+        .find_map(|(i, _)| match_literal(&text[i..], literal).map(|end| (i, i + end)))
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator over the integers in a [`Span`].
+/// See [`Span::unsigned`] and [`Span::signed`].
+pub struct Numbers<'a, T> {
+    span: Span<'a>,
+    pos: usize,
+    signed: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> Numbers<'a, T> {
+    fn new(span: Span<'a>, signed: bool) -> Self {
+        Self {
+            span,
+            pos: 0,
+            signed,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromStr> Numbers<'_, T>
+where
+    T::Err: Display,
+{
+    /// Collects exactly `N` numbers, failing if there are more or fewer.
+    pub fn array<const N: usize>(self) -> Result<[T; N]> {
+        let span = self.span;
+        let values = self.collect::<Result<Vec<T>>>()?;
+        let found = values.len();
+        values
+            .try_into()
+            .map_err(|_| span.error(format!("expected {N} numbers, found {found}")))
+    }
+}
+
+impl<T: FromStr> Iterator for Numbers<'_, T>
+where
+    T::Err: Display,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.span.text.as_bytes();
+        let digit_start = self.pos + bytes[self.pos..].iter().position(u8::is_ascii_digit)?;
+        let end = digit_start
+            + bytes[digit_start..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .unwrap_or(bytes.len() - digit_start);
+        let start = if self.signed && digit_start > self.pos && bytes[digit_start - 1] == b'-' {
+            digit_start - 1
+        } else {
+            digit_start
+        };
+        self.pos = end;
+        Some(self.span.sub(&self.span.text[start..end]).number())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Types that can be parsed from a [`Span`], reporting errors with their position in the input.
+///
+/// Implemented for integers, `char` and `String`. Puzzle structures can derive it with
+/// `#[derive(PuzzleParse)]`, which also implements [`FromStr`] for them.
+pub trait PuzzleParse: Sized {
+    fn parse_span(span: Span<'_>) -> Result<Self>;
+}
+
+pub use advent_of_code_derive::PuzzleParse;
+
+macro_rules! impl_puzzle_parse_from_str {
+    ($($t:ty),*) => {
+        $(
+            impl PuzzleParse for $t {
+                fn parse_span(span: Span<'_>) -> Result<Self> {
+                    span.number()
+                }
+            }
+        )*
+    };
+}
+
+impl_puzzle_parse_from_str!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl PuzzleParse for String {
+    fn parse_span(span: Span<'_>) -> Result<Self> {
+        Ok(span.as_str().to_string())
+    }
+}
+
+impl PuzzleParse for char {
+    fn parse_span(span: Span<'_>) -> Result<Self> {
+        let mut chars = span.as_str().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(span.error(format!("expected a single character, found `{span}`"))),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParseError, Span};
+
+    #[test]
+    fn reports_positions() {
+        let input = Span::new("first line\nsecond line");
+        let second = input.lines().nth(1).unwrap();
+        assert_eq!(second.position(), (2, 1));
+        let word = second.tokens().nth(1).unwrap();
+        assert_eq!(word.as_str(), "line");
+        assert_eq!(
+            word.error("oops"),
+            ParseError {
+                line: 2,
+                column: 8,
+                message: "oops".into()
+            }
+        );
+        assert_eq!(word.error("oops").to_string(), "line 2, column 8: oops");
+        assert_eq!(second.slice(7..11), word);
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let line = Span::new("Game 3: -4 red, 12 blue,7");
+        let unsigned = line.unsigned::<u32>().collect::<Result<Vec<_>, _>>();
+        assert_eq!(unsigned, Ok(vec![3, 4, 12, 7]));
+        let signed = line.signed::<i32>().collect::<Result<Vec<_>, _>>();
+        assert_eq!(signed, Ok(vec![3, -4, 12, 7]));
+        assert_eq!(Span::new("1 2 3").unsigned::<u8>().array(), Ok([1, 2, 3]));
+        assert!(Span::new("1 2").unsigned::<u8>().array::<3>().is_err());
+    }
+
+    #[test]
+    fn reports_number_overflow() {
+        let err = Span::new("1 2\n3 300")
+            .unsigned::<u8>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn parses_headers() {
+        let (id, rest) = Span::new("Card  12: 41 48 | 83").header("Card").unwrap();
+        assert_eq!(id.number::<u32>(), Ok(12));
+        assert_eq!(rest.as_str(), " 41 48 | 83");
+
+        let (label, rest) = Span::new("seeds: 79 14").header("seeds").unwrap();
+        assert!(label.is_empty());
+        assert_eq!(rest.trim().as_str(), "79 14");
+
+        let err = Span::new("x\nGame 1")
+            .lines()
+            .nth(1)
+            .unwrap()
+            .header("Card");
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "line 2, column 1: expected `Card`"
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = Span::new("seeds: 1\n\n\na:\n1 2\n3 4\n\nb:\n5\n");
+        let sections: Vec<_> = input.sections().collect();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[1].as_str(), "a:\n1 2\n3 4");
+        assert_eq!(sections[2].position(), (8, 1));
+    }
+
+    #[test]
+    fn matches_patterns() {
+        let line = Span::new("Card  12:  41 48 | 83 86");
+        let captures = line.match_pattern(&["Card ", ": ", " | ", ""]).unwrap();
+        let captures: Vec<_> = captures.iter().map(|c| c.as_str()).collect();
+        assert_eq!(captures, vec!["12", "41 48", "83 86"]);
+
+        let err = Span::new("Game 1; 3 red")
+            .match_pattern(&["Game ", ": ", ""])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 6: expected `: `");
+
+        let err = Span::new("1 2 3").match_pattern(&["", " ", ""]);
+        assert_eq!(err.unwrap()[1].as_str(), "2 3");
+        assert!(Span::new("1 2 3")
+            .match_pattern(&["", " ", " end"])
+            .is_err());
+    }
+
+    #[test]
+    fn splits_fixed_width() {
+        let chunks: Vec<_> = Span::new("[A] [B] [C]")
+            .chunks(4)
+            .map(|c| c.trim().as_str())
+            .collect();
+        assert_eq!(chunks, vec!["[A]", "[B]", "[C]"]);
+        assert!(Span::new("abc").split_once("|").is_err());
+    }
+}
@@ -0,0 +1,346 @@
+/// Fits the minimal-degree polynomial through an integer sequence and extrapolates it.
+///
+/// The fit uses Newton forward differences: a sequence `f(0), f(1), ..., f(n - 1)` is generated by
+/// a polynomial of degree `d` if its `d + 1`-th differences are all zero. The polynomial is then
+/// `f(x) = Σ Δᵏf(0) · C(x, k)`, which can be evaluated at any integer `x`, including negative
+/// ones. All arithmetic is done in checked `i128`.
+use std::error::Error as StdError;
+use std::fmt::Display;
+
+use crate::checked::{self, Overflow};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input sequence was empty.
+    Empty,
+    /// The differences never reached a row of zeros, so the sequence does not determine a
+    /// polynomial. Holds the number of difference rows that were computed.
+    NotPolynomial(usize),
+    /// An intermediate value did not fit into an `i128`.
+    Overflow(Overflow),
+}
+
+impl StdError for Error {}
+
+impl From<Overflow> for Error {
+    fn from(e: Overflow) -> Self {
+        Error::Overflow(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Empty => write!(f, "cannot fit a polynomial through an empty sequence."),
+            Error::NotPolynomial(rows) => write!(
+                f,
+                "differences did not reach zero after {rows} rows, the sequence is not polynomial."
+            ),
+            Error::Overflow(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A polynomial fitted through the points `(0, f(0)), ..., (n - 1, f(n - 1))`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// `Δᵏf(0)` for `k` in `0..=degree`.
+    newton: Vec<i128>,
+    /// Number of points the polynomial was fitted through.
+    len: usize,
+}
+
+impl Polynomial {
+    /// Fits the minimal-degree polynomial through `values`, sampled at `x = 0, 1, 2, ...`.
+    ///
+    /// A constant zero sequence fits the zero polynomial, which has an empty Newton basis. Fails
+    /// with [`Error::NotPolynomial`] if the differences end in a single nonzero value instead of a
+    /// row of zeros, e.g. for `1 2 4 8 16`, since extrapolating such a sequence is meaningless.
+    pub fn fit<T: Copy + Into<i128>>(values: &[T]) -> Result<Self, Error> {
+        Self::newton(values, true)
+    }
+
+    /// Fits the polynomial of degree at most `values.len() - 1` through `values`, even if the
+    /// differences never reach a row of zeros. `1 2 4` fits `1 + x(x + 1) / 2`.
+    pub fn interpolate<T: Copy + Into<i128>>(values: &[T]) -> Result<Self, Error> {
+        Self::newton(values, false)
+    }
+
+    /// Computes the Newton coefficients of `values`. With `strict`, a last row with a single
+    /// nonzero value is an error.
+    fn newton<T: Copy + Into<i128>>(values: &[T], strict: bool) -> Result<Self, Error> {
+        if values.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let mut row: Vec<i128> = values.iter().map(|&v| v.into()).collect();
+        let mut newton = vec![];
+
+        while !row.iter().all(|&v| v == 0) {
+            if row.len() == 1 && strict {
+                return Err(Error::NotPolynomial(newton.len() + 1));
+            }
+
+            newton.push(row[0]);
+            if row.len() == 1 {
+                break;
+            }
+            row = row
+                .windows(2)
+                .map(|w| checked::sub(w[1], w[0]))
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(Self {
+            newton,
+            len: values.len(),
+        })
+    }
+
+    /// The degree of the polynomial. The zero polynomial is reported as degree `0`.
+    #[must_use]
+    pub fn degree(&self) -> usize {
+        self.newton.len().saturating_sub(1)
+    }
+
+    /// The coefficients `Δᵏf(0)` of the polynomial in the Newton basis `C(x, k)`.
+    #[must_use]
+    pub fn newton_coefficients(&self) -> &[i128] {
+        &self.newton
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn eval(&self, x: i128) -> Result<i128, Error> {
+        let mut binomial: i128 = 1;
+        let mut acc: i128 = 0;
+
+        for (k, &coefficient) in self.newton.iter().enumerate() {
+            if k > 0 {
+                // C(x, k) = C(x, k - 1) * (x - k + 1) / k, which divides exactly for integer x.
+                let k = k as i128;
+                let factor = checked::sub(x, k - 1)?;
+                binomial = checked::mul(binomial, factor)? / k;
+            }
+            let term = checked::mul(coefficient, binomial)?;
+            acc = checked::add(acc, term)?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Returns the value `steps` positions after the last fitted value.
+    pub fn forward(&self, steps: u64) -> Result<i128, Error> {
+        self.eval(self.len as i128 - 1 + i128::from(steps))
+    }
+
+    /// Returns the value `steps` positions before the first fitted value.
+    pub fn backward(&self, steps: u64) -> Result<i128, Error> {
+        self.eval(-i128::from(steps))
+    }
+
+    /// Returns the coefficients of the polynomial in the monomial basis, lowest power first.
+    pub fn coefficients(&self) -> Result<Vec<Rational>, Error> {
+        let degree = self.degree();
+        let mut factorial: i128 = 1;
+        let mut factorials = vec![1];
+        for k in 1..=degree as i128 {
+            factorial = checked::mul(factorial, k)?;
+            factorials.push(factorial);
+        }
+        let denominator = factorials[degree];
+
+        // `falling` holds the coefficients of x(x - 1)...(x - k + 1), lowest power first.
+        let mut falling: Vec<i128> = vec![1];
+        let mut numerators: Vec<i128> = vec![0; degree + 1];
+
+        for (k, &coefficient) in self.newton.iter().enumerate() {
+            if k > 0 {
+                falling = multiply_by_linear(&falling, -(k as i128 - 1))?;
+            }
+            let scale = checked::mul(coefficient, denominator / factorials[k])?;
+            for (numerator, &f) in numerators.iter_mut().zip(&falling) {
+                let term = checked::mul(f, scale)?;
+                *numerator = checked::add(*numerator, term)?;
+            }
+        }
+
+        // the denominator is positive, so reducing the fractions cannot overflow.
+        Ok(numerators
+            .into_iter()
+            .map(|n| Rational::new(n, denominator))
+            .collect())
+    }
+}
+
+/// Multiplies a polynomial (lowest power first) by `x + c`.
+fn multiply_by_linear(poly: &[i128], c: i128) -> Result<Vec<i128>, Error> {
+    let mut res: Vec<i128> = vec![0; poly.len() + 1];
+    for (i, &p) in poly.iter().enumerate() {
+        res[i + 1] = checked::add(res[i + 1], p)?;
+        let scaled = checked::mul(p, c)?;
+        res[i] = checked::add(res[i], scaled)?;
+    }
+    Ok(res)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    pub numer: i128,
+    pub denom: i128,
+}
+
+impl Rational {
+    /// Creates a reduced fraction.
+    ///
+    /// # Panics
+    /// Panics if `denom` is zero, or if the reduced fraction does not fit into `i128`, which only
+    /// happens for a denominator of `i128::MIN`.
+    #[must_use]
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "denominator must not be zero");
+        Self::checked_new(numer, denom)
+            .unwrap_or_else(|| panic!("{numer}/{denom} does not fit into an i128 fraction"))
+    }
+
+    /// Creates a reduced fraction, or `None` if `denom` is zero or the reduced fraction does not
+    /// fit into `i128`.
+    #[must_use]
+    pub fn checked_new(numer: i128, denom: i128) -> Option<Self> {
+        if denom == 0 {
+            return None;
+        }
+        // Reduce the magnitudes in u128, where `i128::MIN` has an absolute value.
+        let divisor = gcd(numer.unsigned_abs(), denom.unsigned_abs()).max(1);
+        let numer_abs = numer.unsigned_abs() / divisor;
+        let numer = if (numer < 0) == (denom < 0) {
+            i128::try_from(numer_abs).ok()?
+        } else {
+            0_i128.checked_sub_unsigned(numer_abs)?
+        };
+        Some(Self {
+            numer,
+            denom: i128::try_from(denom.unsigned_abs() / divisor).ok()?,
+        })
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Error, Overflow, Polynomial, Rational};
+
+    #[test]
+    fn extrapolates_linear() {
+        let p = Polynomial::fit(&[0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(p.degree(), 1);
+        assert_eq!(p.forward(1), Ok(18));
+        assert_eq!(p.forward(3), Ok(24));
+        assert_eq!(p.backward(1), Ok(-3));
+    }
+
+    #[test]
+    fn extrapolates_higher_degree() {
+        let p = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(p.degree(), 3);
+        assert_eq!(p.forward(1), Ok(68));
+        assert_eq!(p.backward(1), Ok(5));
+        assert_eq!(p.newton_coefficients(), &[10, 3, 0, 2]);
+    }
+
+    #[test]
+    fn returns_monomial_coefficients() {
+        // triangular numbers: x(x + 1) / 2
+        let p = Polynomial::fit(&[0, 1, 3, 6, 10, 15]).unwrap();
+        assert_eq!(
+            p.coefficients().unwrap(),
+            vec![
+                Rational::new(0, 1),
+                Rational::new(1, 2),
+                Rational::new(1, 2)
+            ]
+        );
+        assert_eq!(Rational::new(2, -4).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn fits_zero_sequence() {
+        let p = Polynomial::fit(&[0, 0, 0]).unwrap();
+        assert_eq!(p.forward(5), Ok(0));
+        assert!(p.coefficients().unwrap().iter().all(|c| c.numer == 0));
+    }
+
+    #[test]
+    fn rejects_non_polynomial_sequences() {
+        assert_eq!(Polynomial::fit::<i32>(&[]), Err(Error::Empty));
+        assert_eq!(
+            Polynomial::fit(&[1, 2, 4, 8, 16]),
+            Err(Error::NotPolynomial(5))
+        );
+    }
+
+    #[test]
+    fn interpolates_full_degree() {
+        // the differences of 1 2 4 end in a single nonzero value: 1 + x(x + 1) / 2
+        assert_eq!(Polynomial::fit(&[1, 2, 4]), Err(Error::NotPolynomial(3)));
+        let p = Polynomial::interpolate(&[1, 2, 4]).unwrap();
+        assert_eq!(p.degree(), 2);
+        assert_eq!(p.newton_coefficients(), &[1, 1, 1]);
+        assert_eq!(p.forward(1), Ok(7));
+        assert_eq!(p.backward(1), Ok(1));
+        assert_eq!(Polynomial::interpolate(&[5]).unwrap().forward(3), Ok(5));
+        assert_eq!(Polynomial::interpolate::<i32>(&[]), Err(Error::Empty));
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let p = Polynomial::fit(&[i128::MAX - 2, i128::MAX - 1, i128::MAX]).unwrap();
+        assert_eq!(
+            p.forward(1).unwrap_err().to_string(),
+            format!(
+                "{} + 3 overflows i128, use a wider type or a BigInt.",
+                i128::MAX - 2
+            )
+        );
+        assert!(matches!(
+            Polynomial::fit(&[i128::MIN, i128::MAX]),
+            Err(Error::Overflow(Overflow { op: '-', .. }))
+        ));
+    }
+
+    #[test]
+    fn reduces_extreme_fractions() {
+        assert_eq!(Rational::new(i128::MIN, 2), Rational::new(i128::MIN / 2, 1));
+        assert_eq!(Rational::new(i128::MIN, i128::MIN), Rational::new(1, 1));
+        assert_eq!(Rational::new(i128::MIN, 1).numer, i128::MIN);
+        assert_eq!(Rational::checked_new(i128::MIN, -1), None);
+        assert_eq!(Rational::checked_new(1, i128::MIN), None);
+        assert_eq!(Rational::checked_new(1, 0), None);
+    }
+}
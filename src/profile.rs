@@ -0,0 +1,360 @@
+/// Per-phase timings inside a solution part.
+///
+/// Solutions open spans with [`span!`](crate::span), e.g. `let _s = span!("parse");`, which time
+/// the rest of the enclosing block. Spans nest into a tree per thread. When the outermost span of
+/// a thread closes, its timings are merged into the part's totals, and spans opened on other
+/// threads (e.g. rayon workers) are attached below the span that is open on the thread running the
+/// part. Spans are only timed if the crate is compiled with the `profile` feature and the solution
+/// runs with `--profile` (`cargo solve <day> --profile`). Otherwise [`span!`](crate::span) returns
+/// an empty guard, so benchmarks are unaffected.
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// The summed time of a span, by its path from the outermost span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub path: Vec<&'static str>,
+    pub total: Duration,
+    pub count: u32,
+    /// The number of threads the span ran on. Times are summed over all of them.
+    pub threads: usize,
+}
+
+/// Times the rest of the enclosing block while it is alive, see [`span!`](crate::span).
+#[must_use = "a span ends when it is dropped"]
+pub struct SpanGuard {
+    #[cfg(feature = "profile")]
+    active: bool,
+    // spans must end on the thread that opened them.
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Opens a span that is timed until the returned guard is dropped: `let _s = span!("parse");`.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::profile::enter($name)
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "profile")]
+mod recorder {
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashSet;
+    use std::marker::PhantomData;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+    use std::thread::{self, ThreadId};
+    use std::time::{Duration, Instant};
+
+    use super::{SpanGuard, Timing};
+
+    type Path = Vec<&'static str>;
+
+    static RECORDING: AtomicBool = AtomicBool::new(false);
+    /// The path of the spans open on the thread running the part.
+    static ROOT_PATH: Mutex<Path> = Mutex::new(Vec::new());
+    static TIMINGS: Mutex<Vec<(Timing, HashSet<ThreadId>)>> = Mutex::new(Vec::new());
+
+    #[derive(Default)]
+    struct Local {
+        /// Where the outermost open span of this thread is attached.
+        prefix: Path,
+        open: Vec<(&'static str, Instant)>,
+        done: Vec<(Path, Duration, u32)>,
+    }
+
+    impl Local {
+        fn path(&self) -> Path {
+            let names = self.open.iter().map(|&(name, _)| name);
+            self.prefix.iter().copied().chain(names).collect()
+        }
+    }
+
+    thread_local! {
+        static LOCAL: RefCell<Local> = RefCell::new(Local::default());
+        static IS_ROOT: Cell<bool> = const { Cell::new(false) };
+    }
+
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    pub fn enter(name: &'static str) -> SpanGuard {
+        let active = RECORDING.load(Ordering::Relaxed);
+        if active {
+            let is_root = IS_ROOT.get();
+            LOCAL.with_borrow_mut(|local| {
+                if local.open.is_empty() {
+                    local.prefix = if is_root {
+                        vec![]
+                    } else {
+                        ROOT_PATH.lock().unwrap().clone()
+                    };
+                }
+                local.open.push((name, Instant::now()));
+                if is_root {
+                    *ROOT_PATH.lock().unwrap() = local.path();
+                }
+            });
+        }
+        SpanGuard {
+            active,
+            _not_send: PhantomData,
+        }
+    }
+
+    impl Drop for SpanGuard {
+        fn drop(&mut self) {
+            if self.active {
+                LOCAL.with_borrow_mut(exit);
+            }
+        }
+    }
+
+    fn exit(local: &mut Local) {
+        let path = local.path();
+        let Some((_, start)) = local.open.pop() else {
+            return;
+        };
+        let elapsed = start.elapsed();
+
+        match local.done.iter_mut().find(|(p, _, _)| *p == path) {
+            Some((_, total, count)) => {
+                *total += elapsed;
+                *count += 1;
+            }
+            None => local.done.push((path, elapsed, 1)),
+        }
+
+        if IS_ROOT.get() {
+            *ROOT_PATH.lock().unwrap() = local.path();
+        }
+        if local.open.is_empty() {
+            merge(std::mem::take(&mut local.done));
+        }
+    }
+
+    fn merge(done: Vec<(Path, Duration, u32)>) {
+        let thread = thread::current().id();
+        let mut timings = TIMINGS.lock().unwrap();
+        for (path, total, count) in done {
+            match timings.iter_mut().find(|(t, _)| t.path == path) {
+                Some((timing, threads)) => {
+                    timing.total += total;
+                    timing.count += count;
+                    threads.insert(thread);
+                }
+                None => timings.push((
+                    Timing {
+                        path,
+                        total,
+                        count,
+                        threads: 1,
+                    },
+                    HashSet::from([thread]),
+                )),
+            }
+        }
+    }
+
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Timing>) {
+        if !enabled() {
+            return (f(), vec![]);
+        }
+        run(f)
+    }
+
+    /// Records the spans of `f`, whether `--profile` was passed or not.
+    pub(super) fn run<T>(f: impl FnOnce() -> T) -> (T, Vec<Timing>) {
+        TIMINGS.lock().unwrap().clear();
+        IS_ROOT.set(true);
+        RECORDING.store(true, Ordering::Relaxed);
+        let res = f();
+        RECORDING.store(false, Ordering::Relaxed);
+        IS_ROOT.set(false);
+
+        let timings = std::mem::take(&mut *TIMINGS.lock().unwrap())
+            .into_iter()
+            .map(|(timing, threads)| Timing {
+                threads: threads.len(),
+                ..timing
+            })
+            .collect();
+        (res, timings)
+    }
+}
+
+/// Opens a span named `name`, see [`span!`](crate::span).
+#[cfg(feature = "profile")]
+pub fn enter(name: &'static str) -> SpanGuard {
+    recorder::enter(name)
+}
+
+/// Opens a span named `name`, see [`span!`](crate::span).
+#[cfg(not(feature = "profile"))]
+#[inline(always)]
+pub fn enter(_name: &'static str) -> SpanGuard {
+    SpanGuard {
+        _not_send: PhantomData,
+    }
+}
+
+/// Turns profiling on or off for the parts that run after, as `--profile` does.
+pub fn set_enabled(enabled: bool) {
+    #[cfg(feature = "profile")]
+    recorder::set_enabled(enabled);
+    #[cfg(not(feature = "profile"))]
+    let _ = enabled;
+}
+
+/// Runs `f` and returns the timings of its spans. Returns no timings if profiling is off.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Timing>) {
+    #[cfg(feature = "profile")]
+    return recorder::record(f);
+    #[cfg(not(feature = "profile"))]
+    return (f(), vec![]);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the timings of a part as a tree, with percentages of the part's `total` time.
+#[must_use]
+pub fn render(timings: &[Timing], total: Duration, part: u8) -> String {
+    if timings.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("{ANSI_ITALIC}Part {part} profile ({total:.1?}):{ANSI_RESET}\n");
+    render_children(&mut out, timings, &[], total);
+
+    let spanned: Duration = timings
+        .iter()
+        .filter(|t| t.path.len() == 1)
+        .map(|t| t.total)
+        .sum();
+    if let Some(rest) = total.checked_sub(spanned).filter(|d| !d.is_zero()) {
+        let _ = writeln!(out, "{}", line(1, "(outside of spans)", rest, total, ""));
+    }
+    out
+}
+
+/// Renders the timings directly below `parent` in the order they were first recorded, and theirs.
+fn render_children(out: &mut String, timings: &[Timing], parent: &[&str], total: Duration) {
+    let children = timings
+        .iter()
+        .filter(|t| t.path.len() == parent.len() + 1 && t.path.starts_with(parent));
+
+    for timing in children {
+        let mut details = vec![];
+        if timing.count > 1 {
+            details.push(format!("{} calls", timing.count));
+        }
+        if timing.threads > 1 {
+            details.push(format!("summed over {} threads", timing.threads));
+        }
+        let details = if details.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", details.join(", "))
+        };
+
+        let name = timing.path.last().unwrap();
+        let depth = timing.path.len();
+        let _ = writeln!(out, "{}", line(depth, name, timing.total, total, &details));
+        render_children(out, timings, &timing.path, total);
+    }
+}
+
+fn line(depth: usize, name: &str, time: Duration, total: Duration, details: &str) -> String {
+    let percent = 100.0 * time.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON);
+    let label = format!("{}{name}", "  ".repeat(depth));
+    format!(
+        "{label:<24} {:>10} {percent:>6.1}%{details}",
+        format!("{time:.1?}")
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, Timing};
+
+    fn timing(path: &[&'static str], ms: u64, count: u32, threads: usize) -> Timing {
+        Timing {
+            path: path.to_vec(),
+            total: Duration::from_millis(ms),
+            count,
+            threads,
+        }
+    }
+
+    #[test]
+    fn renders_nested_timings() {
+        // children finish before their parents, so they are recorded first.
+        let timings = [
+            timing(&["parse"], 2, 1, 1),
+            timing(&["solve", "walk"], 12, 6, 4),
+            timing(&["solve"], 5, 1, 1),
+        ];
+        assert_eq!(
+            render(&timings, Duration::from_millis(10), 2),
+            "\x1b[3mPart 2 profile (10.0ms):\x1b[0m\n  \
+             parse                       2.0ms   20.0%\n  \
+             solve                       5.0ms   50.0%\n    \
+             walk                     12.0ms  120.0% (6 calls, summed over 4 threads)\n  \
+             (outside of spans)          3.0ms   30.0%\n"
+        );
+        assert_eq!(render(&[], Duration::from_millis(10), 2), "");
+    }
+
+    #[cfg(feature = "profile")]
+    #[test]
+    fn aggregates_spans_across_threads() {
+        use rayon::prelude::*;
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let ((), timings) = super::recorder::run(|| {
+            let _s = crate::span!("walks");
+            pool.install(|| {
+                (0..16).into_par_iter().for_each(|_| {
+                    let _s = crate::span!("walk");
+                    std::thread::sleep(Duration::from_millis(5));
+                });
+            });
+        });
+
+        let paths: Vec<_> = timings.iter().map(|t| t.path.clone()).collect();
+        assert_eq!(paths, vec![vec!["walks", "walk"], vec!["walks"]]);
+        assert_eq!(timings[0].count, 16);
+        assert!(timings[0].threads > 1);
+        assert!(timings[0].total >= Duration::from_millis(80));
+    }
+
+    #[test]
+    fn records_nothing_without_profile_flag() {
+        let (res, timings) = super::record(|| {
+            let _s = crate::span!("parse");
+            3
+        });
+        assert_eq!(res, 3);
+        assert!(timings.is_empty());
+    }
+}
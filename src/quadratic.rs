@@ -0,0 +1,107 @@
+/// Exact integer solutions for quadratic inequalities, without floating point.
+///
+/// Day 06 asks how many ways there are to beat a race record: holding the button for `x` of `t`
+/// milliseconds travels `x * (t - x)`. Real inputs have `t` and `d` in the order of 10^14, where
+/// `f32`/`f64` roots lose the precision needed to decide the boundary cases.
+use std::ops::RangeInclusive;
+
+/// Returns the largest `r` such that `r * r <= n`.
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting from a power of two that is guaranteed to be above the root.
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Counts the integers `x` in `0..=t` for which `x * (t - x) > d`.
+#[must_use]
+pub fn count_above(t: u64, d: u64) -> u64 {
+    above(t, d).map_or(0, |range| range.end() - range.start() + 1)
+}
+
+/// The integers `x` in `0..=t` for which `x * (t - x) > d`, or `None` if there are none.
+#[must_use]
+pub fn above(t: u64, d: u64) -> Option<RangeInclusive<u64>> {
+    let (t, d) = (u128::from(t), u128::from(d));
+    let value = |x: u128| x * (t - x);
+
+    // the parabola peaks at `t / 2`, if that doesn't beat `d` nothing does.
+    let peak = t / 2;
+    if value(peak) <= d {
+        return None;
+    }
+
+    // the smaller root of x² - tx + d = 0 is (t - √(t² - 4d)) / 2. `value(peak) > d` guarantees
+    // that the discriminant is positive. The integer root can be off by one, so we correct it.
+    let root = isqrt(t * t - 4 * d);
+    let mut low = (t - root) / 2;
+    while low > 0 && value(low - 1) > d {
+        low -= 1;
+    }
+    while value(low) <= d {
+        low += 1;
+    }
+
+    // solutions are symmetric around the peak.
+    Some(low as u64..=(t - low) as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{above, count_above, isqrt};
+
+    fn brute_force(t: u64, d: u64) -> u64 {
+        (0..=t).filter(|x| x * (t - x) > d).count() as u64
+    }
+
+    #[test]
+    fn isqrt_exact_and_inexact() {
+        for n in 0..10_000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt(1 << 100), 1 << 50);
+    }
+
+    #[test]
+    fn matches_brute_force_for_small_t() {
+        for t in 0..=120 {
+            for d in 0..=(t * t / 4 + 2) {
+                assert_eq!(count_above(t, d), brute_force(t, d), "t={t}, d={d}");
+            }
+        }
+    }
+
+    #[test]
+    fn solves_example_races() {
+        assert_eq!(count_above(7, 9), 4);
+        assert_eq!(above(7, 9), Some(2..=5));
+        assert_eq!(above(7, 12), None);
+        assert_eq!(count_above(15, 40), 8);
+        assert_eq!(count_above(30, 200), 9);
+        assert_eq!(count_above(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn handles_large_values() {
+        // x * (t - x) > d  <=>  x in 1..t for d = 0.
+        assert_eq!(count_above(u64::MAX, 0), u64::MAX - 1);
+        // exact boundary: x = 10^7 gives exactly 10^14, which does not beat the record.
+        let t = 2 * 10_000_000;
+        assert_eq!(count_above(t, 10u64.pow(14)), 0);
+        assert_eq!(count_above(t, 10u64.pow(14) - 1), 1);
+    }
+}
@@ -0,0 +1,75 @@
+/// A global allocator that measures the peak heap memory of solution parts.
+///
+/// With the `memory` feature, the [`solution!`](crate::solution) macro installs [`PeakAlloc`] in
+/// every day's binary. It forwards to the system allocator and only counts bytes while [`measure`]
+/// runs, but still adds a check to every allocation, so `cargo time` only enables the feature if
+/// the benchmark table has a `memory` column.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+/// Bytes allocated since tracking started, negative if more were freed than allocated.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+pub struct PeakAlloc;
+
+impl PeakAlloc {
+    #[allow(clippy::cast_possible_wrap)]
+    fn track(delta: isize) {
+        if TRACKING.load(Ordering::Relaxed) {
+            let current = CURRENT.fetch_add(delta, Ordering::Relaxed) + delta;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+}
+
+// SAFETY: every call is forwarded to the system allocator unchanged.
+#[allow(clippy::cast_possible_wrap)]
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::track(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::track(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::track(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` and returns the peak number of heap bytes it allocated on top of what was allocated
+/// before. Returns `None` if [`PeakAlloc`] is not the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    let ((), probe) = track(|| drop(std::hint::black_box(Box::new(0u64))));
+    let (res, peak) = track(f);
+    (res, (probe > 0).then_some(peak))
+}
+
+fn track<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    TRACKING.store(true, Ordering::Relaxed);
+    let res = f();
+    TRACKING.store(false, Ordering::Relaxed);
+    (res, PEAK.load(Ordering::Relaxed).max(0).unsigned_abs())
+}
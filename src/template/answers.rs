@@ -0,0 +1,187 @@
+/// Answers of puzzles, and the ones that Advent of Code confirmed as correct.
+///
+/// An [`Answer`] is the normalized text of a solution's result. The runner prints it, `--submit`
+/// sends it, snapshots compare it and the store keeps it, so they all agree on its formatting.
+/// Results are turned into answers by [`ToAnswer`], which only writes integer types in their
+/// canonical form and keeps text like `007` as it is.
+///
+/// Confirmed answers are stored in `data/answers/<day>-<part>.txt` (see [`config::Data`]). They
+/// are recorded when `--submit` gets a correct verdict, and can be written by hand for parts
+/// solved elsewhere.
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::bigint::BigInt;
+use crate::template::config;
+use crate::Day;
+
+/// The normalized text of an answer: without surrounding blank lines, trailing whitespace or
+/// `\r\n` line endings, and for integer results in their canonical decimal form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(String);
+
+impl Answer {
+    /// The answer of a text result, which is only trimmed.
+    #[must_use]
+    pub fn new(result: impl Display) -> Self {
+        Self::parse(&result.to_string())
+    }
+
+    /// The answer of an integer result, in its canonical decimal form.
+    #[must_use]
+    pub fn from_int(result: impl Into<BigInt>) -> Self {
+        Self(result.into().to_string())
+    }
+
+    /// The answer written in `text`, e.g. in a file of the store. Like [`Answer::new`], only
+    /// surrounding whitespace and line endings are normalized.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let first = lines.iter().position(|line| !line.is_empty());
+        let last = lines.iter().rposition(|line| !line.is_empty());
+        Self(match (first, last) {
+            (Some(first), Some(last)) if first == last => lines[first].trim_start().to_string(),
+            (Some(first), Some(last)) => lines[first..=last].join("\n"),
+            _ => String::new(),
+        })
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the answer spans several lines, like letters drawn in ASCII art.
+    #[must_use]
+    pub fn is_multiline(&self) -> bool {
+        self.0.contains('\n')
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Results that solutions can return. Integers become [`Answer::from_int`], text becomes
+/// [`Answer::new`]. Other result types can implement it, or return an [`Answer`].
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+macro_rules! impl_to_answer_int {
+    ($($ty:ty),*) => {
+        $(impl ToAnswer for $ty {
+            fn to_answer(&self) -> Answer {
+                Answer::from_int(*self)
+            }
+        })*
+    };
+}
+
+impl_to_answer_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl ToAnswer for BigInt {
+    fn to_answer(&self) -> Answer {
+        Answer::from_int(self.clone())
+    }
+}
+
+macro_rules! impl_to_answer_text {
+    ($($ty:ty),*) => {
+        $(impl ToAnswer for $ty {
+            fn to_answer(&self) -> Answer {
+                Answer::new(self)
+            }
+        })*
+    };
+}
+
+impl_to_answer_text!(str, String, char);
+
+impl ToAnswer for Answer {
+    fn to_answer(&self) -> Answer {
+        self.clone()
+    }
+}
+
+impl<T: ToAnswer + ?Sized> ToAnswer for &T {
+    fn to_answer(&self) -> Answer {
+        (**self).to_answer()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[must_use]
+pub fn path(day: Day, part: u8) -> PathBuf {
+    config::get()
+        .data
+        .folder("answers")
+        .join(format!("{day}-{part}.txt"))
+}
+
+/// The confirmed answer of a part, if there is one.
+#[must_use]
+pub fn read(day: Day, part: u8) -> Option<Answer> {
+    let answer = Answer::parse(&fs::read_to_string(path(day, part)).ok()?);
+    (!answer.is_empty()).then_some(answer)
+}
+
+pub fn record(day: Day, part: u8, answer: &Answer) -> io::Result<()> {
+    let path = path(day, part);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{answer}\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, ToAnswer};
+    use crate::bigint::BigInt;
+
+    #[test]
+    fn normalizes_integers() {
+        assert_eq!(42_u32.to_answer().as_str(), "42");
+        assert_eq!(Answer::from_int(-0_i64).as_str(), "0");
+        assert_eq!(Answer::parse("-12\r\n"), (-12_i64).to_answer());
+        let big: BigInt = "+000123456789012345678901234567890".parse().unwrap();
+        assert_eq!(
+            big.to_answer(),
+            Answer::parse("123456789012345678901234567890")
+        );
+    }
+
+    #[test]
+    fn keeps_leading_zeros_of_text() {
+        assert_eq!("007".to_answer().as_str(), "007");
+        assert_eq!(String::from(" 01029498\n").to_answer().as_str(), "01029498");
+        assert_eq!(Answer::parse("+007").as_str(), "+007");
+    }
+
+    #[test]
+    fn normalizes_text() {
+        assert_eq!(Answer::parse("  abc \r\n").as_str(), "abc");
+        assert_eq!(Answer::parse("1,2,3").as_str(), "1,2,3");
+        assert!(Answer::parse("\n \n").is_empty());
+        assert!(!Answer::new("abc").is_multiline());
+    }
+
+    #[test]
+    fn keeps_multiline_answers() {
+        let answer = Answer::parse("\n #.# \r\n\n##\n\n");
+        assert_eq!(answer.as_str(), " #.#\n\n##");
+        assert!(answer.is_multiline());
+    }
+}
@@ -1,12 +1,35 @@
-use std::io;
+use std::path::Path;
+use std::{fs, io, process};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    commands::report,
+    config::{self, Column},
+    readme::{Mode, Status},
+    readme_benchmarks::{self, PartStats, Timings},
+    report::parse_history,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Where timed runs save the measurements of every part, see [`crate::template::runner`].
+pub const SAMPLES_DIR: &str = "target/aoc-samples";
+
+/// Runs all solutions. Timed release runs update the benchmarks in the readme, or with `is_check`
+/// exit with an error if they are out of date.
+pub fn handle(is_release: bool, is_timed: bool, is_check: bool) {
+    let config = config::get();
+    let baseline = config
+        .benchmarks
+        .baseline
+        .as_deref()
+        .map(load_baseline)
+        .unwrap_or_default();
+    let samples_dir = is_timed.then_some(Path::new(SAMPLES_DIR));
+    let measure_memory = is_timed && config.benchmarks.columns.contains(&Column::Memory);
+    if is_timed {
+        clear_samples();
+    }
+
     let mut timings: Vec<Timings> = vec![];
 
     all_days().for_each(|day| {
@@ -17,12 +40,28 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output =
+            child_commands::run_solution(day, is_timed, is_release, samples_dir, measure_memory)
+                .unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let mut val = child_commands::parse_exec_time(&output, day);
+            if let Some(dir) = samples_dir {
+                val.stats = [1, 2].map(|part| {
+                    let samples = child_commands::read_samples(dir, day, part)?;
+                    let memory =
+                        child_commands::read_value(dir, &format!("{day}-{part}-memory.txt"));
+                    PartStats::from_samples(&samples, memory)
+                });
+                val.parse_nanos = child_commands::read_value(dir, &format!("{day}-parse.txt"))
+                    .map(|nanos| nanos as f64);
+            }
+            val.baseline_nanos = baseline
+                .iter()
+                .find(|(d, _)| *d == day)
+                .map(|&(_, nanos)| nanos);
             timings.push(val);
         }
     });
@@ -33,16 +72,62 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+            let mode = if is_check { Mode::Check } else { Mode::Write };
+            match readme_benchmarks::update(timings, total_millis, config, mode) {
+                Ok(Status::Updated) => println!("Successfully updated README with benchmarks."),
+                Ok(Status::Unchanged) => println!("README benchmarks are up to date."),
+                Ok(Status::Stale) => {
+                    eprintln!(
+                        "README benchmarks are out of date. Run `cargo time` to update them."
+                    );
+                    process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}");
+                    if is_check {
+                        process::exit(1);
+                    }
                 }
             }
         }
     }
 }
 
+/// Removes the measurements of earlier runs from [`SAMPLES_DIR`].
+pub fn clear_samples() {
+    if let Err(e) = fs::remove_dir_all(SAMPLES_DIR) {
+        if e.kind() != io::ErrorKind::NotFound {
+            eprintln!("Failed to clear old samples: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The total median time of every day at `commit`, from the history that `cargo timings` records.
+fn load_baseline(commit: &str) -> Vec<(Day, f64)> {
+    let path = report::history_path();
+    let history = fs::read_to_string(&path)
+        .map(|csv| parse_history(&csv))
+        .unwrap_or_default();
+
+    let mut totals: Vec<(Day, f64)> = vec![];
+    for entry in history.iter().filter(|e| e.commit == commit) {
+        let nanos = entry.median_nanos as f64;
+        match totals.iter_mut().find(|(day, _)| *day == entry.day) {
+            Some((_, total)) => *total += nanos,
+            None => totals.push((entry.day, nanos)),
+        }
+    }
+
+    if totals.is_empty() {
+        eprintln!(
+            "No timings of the baseline commit {commit} in \"{}\".",
+            path.display()
+        );
+    }
+    totals
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -63,18 +148,29 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
+pub(crate) mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::runner::RunOptions;
     use crate::Day;
     use std::{
+        fs,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// With a `samples_dir`, the bin writes the durations of its benchmark samples there.
+    /// `measure_memory` builds it with the `memory` feature, which installs the allocator that
+    /// measures the peak memory of each part.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        samples_dir: Option<&Path>,
+        measure_memory: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -86,18 +182,27 @@ mod child_commands {
         if is_release {
             args.push("--release");
         }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if measure_memory {
+            args.extend(["--features", "memory"]);
         }
+        args.push("--");
+
+        let options = RunOptions {
+            time: is_timed,
+            ..RunOptions::default()
+        };
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new("cargo");
+        if let Some(dir) = samples_dir {
+            cmd.env("AOC_SAMPLES_DIR", dir);
+        }
+
+        let mut cmd = cmd
             .args(&args)
+            .args(options.to_args())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -125,12 +230,28 @@ mod child_commands {
         Ok(output)
     }
 
+    /// The nanoseconds of every benchmark sample that the bin saved for a part.
+    pub fn read_samples(dir: &Path, day: Day, part: u8) -> Option<Vec<u64>> {
+        let content = fs::read_to_string(dir.join(format!("{day}-{part}.txt"))).ok()?;
+        let nanos: Vec<u64> = content.lines().filter_map(|l| l.parse().ok()).collect();
+        (!nanos.is_empty()).then_some(nanos)
+    }
+
+    /// A single number that the bin saved, e.g. the peak memory of a part.
+    pub fn read_value(dir: &Path, name: &str) -> Option<u64> {
+        fs::read_to_string(dir.join(name)).ok()?.trim().parse().ok()
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_nanos: [None, None],
+            stats: [None, None],
+            parse_nanos: None,
+            baseline_nanos: None,
         };
 
         output
@@ -151,8 +272,10 @@ mod child_commands {
             .for_each(|(part, timing_str, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_nanos[0] = Some(nanos);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_nanos[1] = Some(nanos);
                 }
 
                 timings.total_nanos += nanos;
@@ -171,7 +294,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
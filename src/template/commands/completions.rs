@@ -0,0 +1,8 @@
+use std::io;
+
+use clap_complete::Shell;
+
+/// Prints a completion script of `command` for `shell`, for the command `name` that runs it.
+pub fn handle(shell: Shell, mut command: clap::Command, name: &str) {
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+}
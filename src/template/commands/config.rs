@@ -0,0 +1,27 @@
+use std::env;
+use std::path::PathBuf;
+
+use crate::template::config::{self, OVERRIDES};
+
+/// Prints the effective configuration, with the file it was read from and the overrides.
+pub fn show() {
+    let config = config::get();
+
+    let path = env::var_os("AOC_CONFIG").map_or_else(|| PathBuf::from(config::PATH), PathBuf::from);
+    if path.exists() {
+        println!("# Read from \"{}\".", path.display());
+    } else {
+        println!(
+            "# \"{}\" does not exist, using the defaults.",
+            path.display()
+        );
+    }
+    for (var, setting) in OVERRIDES {
+        if let Ok(value) = env::var(var) {
+            println!("# `{setting}` is overridden by {var}={value}.");
+        }
+    }
+
+    println!();
+    print!("{}", config.to_toml());
+}
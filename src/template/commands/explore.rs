@@ -0,0 +1,18 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::Day;
+
+pub fn handle(day: Day, dot: &Path) {
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--bin", &day.to_string(), "--", "--dot"])
+        .arg(dot)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
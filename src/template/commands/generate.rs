@@ -0,0 +1,46 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, process};
+
+use crate::generate;
+use crate::template::config;
+use crate::Day;
+
+const DEFAULT_SIZE: usize = 10;
+
+pub fn handle(day: Day, seed: Option<u64>, size: Option<usize>) {
+    let Some(generator) = generate::generator(day) else {
+        eprintln!("There is no input generator for day {day}.");
+        process::exit(1);
+    };
+
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let size = size.unwrap_or(DEFAULT_SIZE);
+
+    let generated = generator.generate(seed, size);
+
+    let dir = config::get().data.folder("generated");
+    let input_path = dir.join(format!("{day}-{seed}.txt"));
+    let answers_path = dir.join(format!("{day}-{seed}.answers.txt"));
+    let answers = format!(
+        "Part 1: {}\nPart 2: {}\n",
+        generated.part_one, generated.part_two
+    );
+
+    if let Err(e) = fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&input_path, &generated.input))
+        .and_then(|()| fs::write(&answers_path, &answers))
+    {
+        eprintln!("Failed to write generated input: {e}");
+        process::exit(1);
+    }
+
+    println!("Created input file \"{}\"", input_path.display());
+    println!("Created answers file \"{}\"", answers_path.display());
+    println!("---");
+    println!("🎄 Seed: {seed}, size: {size} ({}).", generator.size_hint);
+    print!("{answers}");
+}
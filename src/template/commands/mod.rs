@@ -1,5 +1,13 @@
 pub mod all;
+pub mod completions;
+pub mod config;
 pub mod download;
+pub mod explore;
+pub mod generate;
+pub mod progress;
 pub mod read;
+pub mod report;
 pub mod scaffold;
+pub mod snapshots;
 pub mod solve;
+pub mod today;
@@ -0,0 +1,92 @@
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::answers::{self, Answer};
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::progress::{self, DayProgress, Status};
+use crate::template::readme::{self, Mode};
+use crate::template::{aoc_cli, config};
+use crate::{all_days, Day};
+
+pub fn handle(update_readme: bool, check: bool) {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("Set `year` in aoc.toml or `AOC_YEAR` to the year you are solving.");
+        process::exit(1);
+    };
+
+    let progress: Vec<DayProgress> = all_days().map(day_progress).collect();
+    print!("\r{:<40}\r", "");
+    println!("{}", progress::calendar(&progress, year));
+
+    if !(update_readme || check) {
+        return;
+    }
+
+    let mode = if check { Mode::Check } else { Mode::Write };
+    let readme = &config::get().readme;
+    let status = readme::update(&readme.path, &readme.blocks.stars, mode, |block| {
+        let year = block
+            .attribute("year")
+            .and_then(|year| year.parse().ok())
+            .unwrap_or(year);
+        progress::stars_table(&progress, year)
+    });
+    match status {
+        Ok(readme::Status::Updated) => println!("Successfully updated README with stars."),
+        Ok(readme::Status::Unchanged) => println!("README stars are up to date."),
+        Ok(readme::Status::Stale) => {
+            eprintln!(
+                "README stars are out of date. Run `cargo progress --readme` to update them."
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn day_progress(day: Day) -> DayProgress {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        return DayProgress::missing(day);
+    }
+
+    print!("\rRunning day {day}...");
+    let _ = stdout().flush();
+
+    let results = run_solution(day);
+    let parts = [1, 2].map(|part| {
+        let result: Option<Option<Answer>> = results
+            .as_ref()
+            .map(|results| results[part as usize - 1].as_deref().map(Answer::parse));
+        let confirmed = answers::read(day, part);
+        Status::of(
+            result
+                .as_ref()
+                .map(|result| result.as_ref().map(Answer::as_str)),
+            confirmed.as_ref().map(Answer::as_str),
+        )
+    });
+    DayProgress { day, parts }
+}
+
+/// Runs the optimized solution of a day once, `None` if it could not run (e.g. without input).
+fn run_solution(day: Day) -> Option<[Option<String>; 2]> {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day.to_string()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    Some(progress::parse_results(&lines))
+}
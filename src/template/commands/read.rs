@@ -1,16 +1,109 @@
-use std::process;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{stdout, IsTerminal, Write};
+use std::process::{self, Command, Stdio};
 
-use crate::template::aoc_cli;
+use crate::template::puzzle::Puzzle;
+use crate::template::{config, ANSI_BOLD, ANSI_RESET};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Lines are not wrapped wider than this, even in wide terminals.
+const MAX_WIDTH: usize = 100;
+
+/// Prints the stored description of a puzzle, or its examples, and pages it if it does not fit
+/// in the terminal.
+pub fn handle(day: Day, part: Option<u8>, examples: bool) {
+    let path = config::get().data.puzzle(day);
+    let Ok(markdown) = fs::read_to_string(&path) else {
+        eprintln!(
+            "Could not read the description at \"{}\". Download it with `cargo download {day}`.",
+            path.display()
+        );
         process::exit(1);
-    }
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let puzzle = Puzzle::parse(&markdown);
+    if let Some(part) = part.filter(|&part| !puzzle.has_part(part)) {
+        eprintln!(
+            "Part {part} is not in \"{}\" yet. Download the description again after solving part 1.",
+            path.display()
+        );
         process::exit(1);
+    }
+
+    let color = stdout().is_terminal();
+    let text = if examples {
+        list_examples(&puzzle, part, color)
+    } else {
+        let width = terminal_size().map_or(80, |(_, columns)| columns);
+        puzzle.render(part, width.min(MAX_WIDTH), color)
     };
+
+    if text.is_empty() {
+        eprintln!("There is nothing to show in \"{}\".", path.display());
+        process::exit(1);
+    }
+    page(&text);
+}
+
+fn list_examples(puzzle: &Puzzle, part: Option<u8>, color: bool) -> String {
+    let mut out = String::new();
+    for example in puzzle.examples(part) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let title = format!("Example {} (part {}):", example.index, example.part);
+        if color {
+            let _ = writeln!(out, "{ANSI_BOLD}{title}{ANSI_RESET}");
+        } else {
+            let _ = writeln!(out, "{title}");
+        }
+        let _ = writeln!(out, "{}", example.text);
+    }
+    out
+}
+
+/// Writes `text` to `$PAGER`, or `less -R`, if it is longer than the terminal is high.
+fn page(text: &str) {
+    let mut stdout = stdout();
+    let fits = terminal_size().is_none_or(|(rows, _)| text.lines().count() < rows);
+
+    if stdout.is_terminal() && !fits {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+        let mut words = pager.split_whitespace();
+        if let Some(program) = words.next() {
+            if let Ok(mut child) = Command::new(program)
+                .args(words)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = stdin.write_all(text.as_bytes());
+                }
+                let _ = child.wait();
+                return;
+            }
+        }
+    }
+
+    let _ = stdout.write_all(text.as_bytes());
+}
+
+/// The rows and columns of the terminal, from `LINES` and `COLUMNS` or else `stty size`.
+fn terminal_size() -> Option<(usize, usize)> {
+    let var = |name| env::var(name).ok().and_then(|v| v.parse().ok());
+    if let (Some(rows), Some(columns)) = (var("LINES"), var("COLUMNS")) {
+        return Some((rows, columns));
+    }
+
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let size = String::from_utf8_lossy(&output.stdout);
+    let (rows, columns) = size.trim().split_once(' ')?;
+    Some((rows.parse().ok()?, columns.parse().ok()?))
 }
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use crate::template::commands::all::{self, child_commands, SAMPLES_DIR};
+use crate::template::report::{self, HistoryEntry, Samples};
+use crate::template::{config, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// The medians of every commit, in the `timings` data folder.
+#[must_use]
+pub fn history_path() -> PathBuf {
+    config::get().data.folder("timings").join("history.csv")
+}
+
+pub fn handle(html: &Path) {
+    let samples_dir = Path::new(SAMPLES_DIR);
+    all::clear_samples();
+
+    let mut samples = vec![];
+    for day in all_days() {
+        if day > 1 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let output =
+            child_commands::run_solution(day, true, true, Some(samples_dir), false).unwrap();
+        if output.is_empty() {
+            println!("Not solved.");
+        }
+        samples.extend((1..=2).filter_map(|part| read_samples(samples_dir, day, part)));
+    }
+
+    let history = match record_history(&samples) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to update timing history: {e}");
+            vec![]
+        }
+    };
+
+    let title = config::get().year.map_or_else(
+        || "Advent of Code benchmarks".to_string(),
+        |year| format!("Advent of Code {year} benchmarks"),
+    );
+    let path = html.join("index.html");
+    if let Err(e) = fs::create_dir_all(html)
+        .and_then(|()| fs::write(&path, report::render(&samples, &history, &title)))
+    {
+        eprintln!("Failed to write report: {e}");
+        process::exit(1);
+    }
+    println!("\nWrote benchmark report to \"{}\"", path.display());
+}
+
+fn read_samples(dir: &Path, day: Day, part: u8) -> Option<Samples> {
+    let nanos = child_commands::read_samples(dir, day, part)?;
+    Some(Samples { day, part, nanos })
+}
+
+/// Replaces the medians of the current commit in the history file and returns the whole history.
+/// Outside of a git repository, the history is read but not updated.
+fn record_history(samples: &[Samples]) -> io::Result<Vec<HistoryEntry>> {
+    let path = history_path();
+    let mut history = match fs::read_to_string(&path) {
+        Ok(csv) => report::parse_history(&csv),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => return Err(e),
+    };
+
+    let Some(commit) = current_commit() else {
+        return Ok(history);
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    history.retain(|e| e.commit != commit);
+    history.extend(samples.iter().map(|s| HistoryEntry {
+        commit: commit.clone(),
+        timestamp,
+        day: s.day,
+        part: s.part,
+        median_nanos: s.median(),
+    }));
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, report::format_history(&history))?;
+    Ok(history)
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
@@ -0,0 +1,26 @@
+use std::process::{self, Command, Stdio};
+
+use crate::Day;
+
+/// Runs the tests of a day, or of all days. With `bless`, the snapshots that they check are
+/// updated with the answers, see [`crate::template::snapshot`].
+pub fn handle(day: Option<Day>, bless: bool) {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("test");
+    match day {
+        Some(day) => cmd.args(["--bin", &day.to_string()]),
+        None => cmd.arg("--bins"),
+    };
+    if bless {
+        cmd.env("AOC_BLESS", "1");
+    }
+
+    let status = cmd
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
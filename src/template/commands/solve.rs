@@ -1,31 +1,52 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
+use crate::template::runner::RunOptions;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if release {
-        cmd_args.push("--release".to_string());
-    }
+/// The options of `cargo solve`.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct Options {
+    /// Build an optimized binary, required to submit.
+    #[arg(long, required_if_eq_any([("submit", "1"), ("submit", "2")]))]
+    pub release: bool,
+    #[command(flatten)]
+    pub run: RunOptions,
+}
 
-    cmd_args.push("--".to_string());
+pub fn handle(day: Day, options: &Options) {
+    let mut cmd_args = vec!["run".into(), "--bin".into(), day.to_string()];
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if options.release {
+        cmd_args.push("--release".into());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    let run = &options.run;
+    let mut features = vec![];
+    if run.viz || run.export.is_some() {
+        features.push("viz");
+    }
+    if run.explain {
+        features.push("explain");
+    }
+    if run.profile {
+        features.push("profile");
+    }
+    if !features.is_empty() {
+        cmd_args.push("--features".into());
+        cmd_args.push(features.join(","));
     }
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .arg("--")
+        .args(run.to_args())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
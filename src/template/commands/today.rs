@@ -0,0 +1,67 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::commands::{all::get_path_for_bin, scaffold};
+use crate::template::{aoc_cli, release};
+
+/// Scaffolds the puzzle released last, downloads it and opens it in the browser.
+pub fn handle(open: bool) {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("Set `year` in aoc.toml or `AOC_YEAR` to the year you are solving.");
+        process::exit(1);
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX));
+
+    let day = match release::puzzle_day(now, year) {
+        Ok(day) => day,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+    println!(
+        "🎄 Today is day {} of Advent of Code {year}.",
+        day.into_inner()
+    );
+
+    if Path::new(&get_path_for_bin(day)).exists() {
+        println!("Day {day} is scaffolded already.");
+    } else {
+        scaffold::handle(day);
+    }
+
+    if aoc_cli::check().is_ok() {
+        if let Err(e) = aoc_cli::download(day) {
+            eprintln!("Failed to download the puzzle: {e}");
+        }
+    } else {
+        println!("Install aoc-cli to download the input, see \"Configure aoc-cli integration\" in the readme.");
+    }
+
+    let url = release::puzzle_url(year, day);
+    if !open {
+        println!("Puzzle: {url}");
+    } else if !open_url(&url) {
+        println!("Could not open a browser, the puzzle is at {url}");
+    }
+}
+
+fn open_url(url: &str) -> bool {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+    cmd.arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
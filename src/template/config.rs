@@ -0,0 +1,406 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// Every setting is optional. A missing file or section falls back to the defaults. Some settings
+/// can be overridden with environment variables (see [`OVERRIDES`]), which the global flags of the
+/// `advent_of_code` binary set, so the day binaries it runs see the same configuration.
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fs, io, process};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{progress, readme_benchmarks};
+use crate::Day;
+
+pub const PATH: &str = "aoc.toml";
+
+/// Environment variables that override a setting, with the setting they override.
+/// `AOC_CONFIG` reads the file from another path instead of [`PATH`].
+pub const OVERRIDES: [(&str, &str); 5] = [
+    ("AOC_YEAR", "year"),
+    ("AOC_DATA_DIR", "data.dir"),
+    ("AOC_README", "readme.path"),
+    ("AOC_BENCH_BUDGET_MS", "sampling.budget_ms"),
+    ("AOC_SUBMIT_CONFIRM", "submit.confirm"),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    Parse(PathBuf, toml::de::Error),
+    IO(PathBuf, io::Error),
+    Env(&'static str, String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(path, e) => write!(f, "invalid {}: {e}", path.display()),
+            Error::IO(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            Error::Env(var, value) => write!(f, "invalid value `{value}` in {var}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The year of the puzzles, for aoc-cli and links to the puzzles.
+    pub year: Option<u16>,
+    pub data: Data,
+    pub readme: Readme,
+    pub sampling: Sampling,
+    pub benchmarks: BenchmarkTable,
+    pub submit: Submit,
+}
+
+/// The configuration of this process, loaded on first use. Exits if it is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+impl Config {
+    /// Reads the config file and applies the environment overrides. A missing file counts as
+    /// an empty one.
+    pub fn load() -> Result<Self, Error> {
+        let path = env::var_os("AOC_CONFIG").map_or_else(|| PathBuf::from(PATH), PathBuf::from);
+        let config = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|e| Error::Parse(path, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(Error::IO(path, e)),
+        };
+        config.with_overrides(|var| env::var(var).ok())
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        toml::from_str(content).map_err(|e| Error::Parse(PATH.into(), e))
+    }
+
+    /// Applies the [`OVERRIDES`] that `var` returns a value for.
+    pub fn with_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        for (name, _) in OVERRIDES {
+            let Some(value) = var(name) else {
+                continue;
+            };
+            let invalid = || Error::Env(name, value.clone());
+            match name {
+                "AOC_YEAR" => self.year = Some(value.parse().map_err(|_| invalid())?),
+                "AOC_DATA_DIR" => self.data.dir = value.clone().into(),
+                "AOC_README" => self.readme.path = value.clone().into(),
+                "AOC_BENCH_BUDGET_MS" => {
+                    self.sampling.budget_ms = value.parse().map_err(|_| invalid())?;
+                }
+                "AOC_SUBMIT_CONFIRM" => {
+                    self.submit.confirm = value.parse().map_err(|_| invalid())?;
+                }
+                _ => unreachable!("unhandled override {name}"),
+            }
+        }
+        Ok(self)
+    }
+
+    /// Renders the settings as TOML, in the format of the config file.
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("config is serializable")
+    }
+}
+
+/// Where puzzle data is stored, in `[data]`. Folders are relative to `dir`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Data {
+    pub dir: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub answers: PathBuf,
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self {
+            dir: "data".into(),
+            inputs: "inputs".into(),
+            examples: "examples".into(),
+            puzzles: "puzzles".into(),
+            answers: "answers".into(),
+        }
+    }
+}
+
+impl Data {
+    /// The folder `name`, e.g. `inputs`. Folders without a setting (`generated`, `regressions`,
+    /// `snapshots`, `timings`) keep their name.
+    #[must_use]
+    pub fn folder(&self, name: &str) -> PathBuf {
+        let folder = match name {
+            "inputs" => &self.inputs,
+            "examples" => &self.examples,
+            "puzzles" => &self.puzzles,
+            "answers" => &self.answers,
+            _ => Path::new(name),
+        };
+        self.dir.join(folder)
+    }
+
+    #[must_use]
+    pub fn input(&self, day: Day) -> PathBuf {
+        self.folder("inputs").join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn example(&self, day: Day) -> PathBuf {
+        self.folder("examples").join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn puzzle(&self, day: Day) -> PathBuf {
+        self.folder("puzzles").join(format!("{day}.md"))
+    }
+}
+
+/// The readme that generated blocks are written to, in `[readme]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Readme {
+    pub path: PathBuf,
+    pub blocks: Blocks,
+}
+
+impl Default for Readme {
+    fn default() -> Self {
+        Self {
+            path: "README.md".into(),
+            blocks: Blocks::default(),
+        }
+    }
+}
+
+/// The names of the generated blocks, see [`readme`](super::readme).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Blocks {
+    pub benchmarks: String,
+    pub stars: String,
+}
+
+impl Default for Blocks {
+    fn default() -> Self {
+        Self {
+            benchmarks: readme_benchmarks::BLOCK.into(),
+            stars: progress::BLOCK.into(),
+        }
+    }
+}
+
+/// How many samples timed runs take, in `[sampling]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Sampling {
+    /// The time each part is benchmarked for, as far as the sample limits allow.
+    pub budget_ms: u64,
+    pub min_samples: u32,
+    pub max_samples: u32,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl Sampling {
+    /// The number of samples that fit into the budget if one run takes `nanos`.
+    #[must_use]
+    pub fn samples(&self, nanos: u128) -> u32 {
+        let samples = u128::from(self.budget_ms) * 1_000_000 / nanos.max(10);
+        let samples = u32::try_from(samples).unwrap_or(u32::MAX);
+        samples.clamp(self.min_samples, self.max_samples.max(self.min_samples))
+    }
+}
+
+/// The layout of the benchmarks table in the readme, in `[benchmarks]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchmarkTable {
+    /// Columns after `Part 1` and `Part 2`, in order.
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Adds the totals of part 1 and part 2 below the table.
+    pub part_totals: bool,
+    /// A commit in `data/timings/history.csv` to compare against in the `speedup` column.
+    pub baseline: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    /// The number of benchmark samples of each part.
+    Samples,
+    /// The standard deviation of the samples of each part.
+    Stddev,
+    /// The peak heap memory of each part.
+    Memory,
+    /// The duration of the `validate` hook, which parses the input.
+    Parse,
+    /// The time of both parts.
+    Total,
+    /// The share of the day in the total time.
+    Percent,
+    /// How many times faster the day is than at the baseline commit.
+    Speedup,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sort {
+    #[default]
+    Day,
+    /// The slowest day first.
+    Slowest,
+}
+
+/// What `--submit` does, in `[submit]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Submit {
+    /// Asks before sending an answer.
+    pub confirm: bool,
+    /// Sends answers of parts that already have a confirmed answer, which are skipped otherwise.
+    pub resubmit: bool,
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{BenchmarkTable, Column, Config, Sampling, Sort};
+    use crate::day;
+
+    #[test]
+    fn parses_benchmark_table() {
+        let config = Config::parse(
+            r#"
+            [benchmarks]
+            columns = ["samples", "percent", "speedup"]
+            sort = "slowest"
+            part_totals = true
+            baseline = "abc1234"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.benchmarks,
+            BenchmarkTable {
+                columns: vec![Column::Samples, Column::Percent, Column::Speedup],
+                sort: Sort::Slowest,
+                part_totals: true,
+                baseline: Some("abc1234".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_project_settings() {
+        let config = Config::parse(
+            r#"
+            year = 2022
+
+            [data]
+            dir = "puzzles"
+            inputs = "private"
+
+            [readme]
+            path = "docs/README.md"
+            blocks.stars = "progress"
+
+            [submit]
+            confirm = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(
+            config.data.input(day!(3)),
+            PathBuf::from("puzzles/private/03.txt")
+        );
+        assert_eq!(
+            config.data.example(day!(3)),
+            PathBuf::from("puzzles/examples/03.txt")
+        );
+        assert_eq!(
+            config.data.folder("regressions"),
+            PathBuf::from("puzzles/regressions")
+        );
+        assert_eq!(config.readme.path, PathBuf::from("docs/README.md"));
+        assert_eq!(config.readme.blocks.stars, "progress");
+        assert_eq!(config.readme.blocks.benchmarks, "benchmarks");
+        assert!(config.submit.confirm);
+        assert!(!config.submit.resubmit);
+    }
+
+    #[test]
+    fn defaults_missing_settings() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert_eq!(Config::parse("[benchmarks]").unwrap(), Config::default());
+        assert_eq!(
+            Config::default().data.puzzle(day!(1)),
+            PathBuf::from("data/puzzles/01.md")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        let err = Config::parse("[benchmarks]\ncolumns = [\"speed\"]").unwrap_err();
+        assert!(err.to_string().starts_with("invalid aoc.toml: "));
+        assert!(Config::parse("[benchmarks]\nsorting = \"day\"").is_err());
+        assert!(Config::parse("[readme]\nblocks.progress = \"x\"").is_err());
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let config = Config::parse("year = 2022\n[data]\ndir = \"in\"")
+            .unwrap()
+            .with_overrides(|var| match var {
+                "AOC_YEAR" => Some("2023".into()),
+                "AOC_SUBMIT_CONFIRM" => Some("true".into()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.data.dir, PathBuf::from("in"));
+        assert!(config.submit.confirm);
+
+        let err = Config::default()
+            .with_overrides(|var| (var == "AOC_BENCH_BUDGET_MS").then(|| "1s".into()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid value `1s` in AOC_BENCH_BUDGET_MS");
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let config = Config::parse("year = 2023\n[benchmarks]\ncolumns = [\"memory\"]").unwrap();
+        assert_eq!(Config::parse(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn fits_samples_into_budget() {
+        let sampling = Sampling::default();
+        assert_eq!(sampling.samples(1_000_000), 1000);
+        assert_eq!(sampling.samples(0), 10000);
+        assert_eq!(sampling.samples(u128::from(u64::MAX)), 10);
+    }
+}
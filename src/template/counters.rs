@@ -0,0 +1,253 @@
+/// Hardware performance counters for benchmarked parts.
+///
+/// On Linux, [`Session`] opens counters for instructions, cycles, cache misses and branch misses
+/// of the current thread (and threads it spawns later) with `perf_event_open`. Counters that the
+/// kernel or the hardware doesn't provide, e.g. because of `perf_event_paranoid`, a seccomp filter
+/// or a virtual machine without a PMU, are reported as `n/a`. On other platforms all are `n/a`.
+use std::fmt::{self, Display};
+
+/// Counter values, `None` if the counter is not available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub instructions: Option<u64>,
+    pub cycles: Option<u64>,
+    pub cache_misses: Option<u64>,
+    pub branch_misses: Option<u64>,
+}
+
+impl Counters {
+    /// Divides every counter by the number of `iterations` it was measured over.
+    #[must_use]
+    pub fn per_iteration(self, iterations: u64) -> Self {
+        let per = |value: Option<u64>| value.map(|v| v / iterations.max(1));
+        Self {
+            instructions: per(self.instructions),
+            cycles: per(self.cycles),
+            cache_misses: per(self.cache_misses),
+            branch_misses: per(self.branch_misses),
+        }
+    }
+
+    /// Instructions per cycle, if both are available.
+    #[must_use]
+    pub fn ipc(&self) -> Option<f64> {
+        match (self.instructions, self.cycles) {
+            (Some(instructions), Some(cycles)) if cycles > 0 => {
+                Some(instructions as f64 / cycles as f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for Counters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ipc = self
+            .ipc()
+            .map_or_else(|| "n/a".to_string(), |ipc| format!("{ipc:.2}"));
+        write!(
+            f,
+            "instructions: {} · cycles: {} · IPC: {ipc} · cache misses: {} · branch misses: {}",
+            count(self.instructions),
+            count(self.cycles),
+            count(self.cache_misses),
+            count(self.branch_misses),
+        )
+    }
+}
+
+/// Formats a counter with a metric suffix, e.g. `12.4M`.
+fn count(value: Option<u64>) -> String {
+    let Some(value) = value else {
+        return "n/a".to_string();
+    };
+    match value {
+        0..=9_999 => value.to_string(),
+        10_000..=999_999 => format!("{:.1}k", value as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", value as f64 / 1e6),
+        _ => format!("{:.1}G", value as f64 / 1e9),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Counters that run from [`Session::start`] until [`Session::stop`].
+pub struct Session {
+    #[cfg(target_os = "linux")]
+    counters: [Option<perf::Counter>; 4],
+}
+
+#[cfg(target_os = "linux")]
+impl Session {
+    #[must_use]
+    pub fn start() -> Self {
+        use perf::{Counter, BRANCH_MISSES, CACHE_MISSES, CPU_CYCLES, INSTRUCTIONS};
+
+        let counters = [INSTRUCTIONS, CPU_CYCLES, CACHE_MISSES, BRANCH_MISSES].map(Counter::open);
+        for counter in counters.iter().flatten() {
+            counter.enable();
+        }
+        Self { counters }
+    }
+
+    #[must_use]
+    pub fn stop(self) -> Counters {
+        for counter in self.counters.iter().flatten() {
+            counter.disable();
+        }
+        let [instructions, cycles, cache_misses, branch_misses] = self
+            .counters
+            .map(|counter| counter.and_then(|mut counter| counter.read()));
+        Counters {
+            instructions,
+            cycles,
+            cache_misses,
+            branch_misses,
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Session {
+    #[must_use]
+    pub fn start() -> Self {
+        Self {}
+    }
+
+    #[must_use]
+    pub fn stop(self) -> Counters {
+        Counters::default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod perf {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const CPU_CYCLES: u64 = 0;
+    pub const INSTRUCTIONS: u64 = 1;
+    pub const CACHE_MISSES: u64 = 3;
+    pub const BRANCH_MISSES: u64 = 5;
+
+    const FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+    const FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_INHERIT: u64 = 1 << 1;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+    const IOC_ENABLE: libc::c_ulong = 0x2400;
+    const IOC_DISABLE: libc::c_ulong = 0x2401;
+
+    /// The leading fields of `struct perf_event_attr`, zero-padded to its 128 byte version.
+    #[repr(C)]
+    struct Attr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        rest: [u64; 10],
+    }
+
+    pub struct Counter {
+        file: File,
+    }
+
+    impl Counter {
+        /// Opens a disabled hardware counter for the current thread, or `None` if that fails.
+        pub fn open(config: u64) -> Option<Self> {
+            let attr = Attr {
+                kind: PERF_TYPE_HARDWARE,
+                size: std::mem::size_of::<Attr>() as u32,
+                config,
+                sample_period: 0,
+                sample_type: 0,
+                read_format: FORMAT_TOTAL_TIME_ENABLED | FORMAT_TOTAL_TIME_RUNNING,
+                flags: FLAG_DISABLED | FLAG_INHERIT | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                rest: [0; 10],
+            };
+            // SAFETY: `attr` is a valid `perf_event_attr` that outlives the call.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const Attr,
+                    0,
+                    -1,
+                    -1,
+                    PERF_FLAG_FD_CLOEXEC,
+                )
+            };
+            let fd = i32::try_from(fd).ok().filter(|&fd| fd >= 0)?;
+            // SAFETY: `fd` was just opened and is owned by nothing else.
+            let file = unsafe { File::from_raw_fd(fd) };
+            Some(Self { file })
+        }
+
+        pub fn enable(&self) {
+            // SAFETY: the file descriptor belongs to a perf event.
+            unsafe { libc::ioctl(self.file.as_raw_fd(), IOC_ENABLE as _, 0) };
+        }
+
+        pub fn disable(&self) {
+            // SAFETY: the file descriptor belongs to a perf event.
+            unsafe { libc::ioctl(self.file.as_raw_fd(), IOC_DISABLE as _, 0) };
+        }
+
+        /// Reads the counter, scaled up if the kernel only ran it part of the time.
+        pub fn read(&mut self) -> Option<u64> {
+            let mut buf = [0u8; 24];
+            self.file.read_exact(&mut buf).ok()?;
+            let [value, enabled, running] =
+                [0, 8, 16].map(|i| u64::from_ne_bytes(buf[i..i + 8].try_into().unwrap()));
+            if running == 0 {
+                return None;
+            }
+            Some((u128::from(value) * u128::from(enabled) / u128::from(running)) as u64)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Counters, Session};
+
+    #[test]
+    fn formats_counters() {
+        let counters = Counters {
+            instructions: Some(24_800_000),
+            cycles: Some(12_400_000),
+            cache_misses: Some(12_345),
+            branch_misses: None,
+        };
+        assert_eq!(
+            counters.per_iteration(2).to_string(),
+            "instructions: 12.4M · cycles: 6.2M · IPC: 2.00 · cache misses: 6172 · branch misses: n/a"
+        );
+        assert_eq!(
+            Counters::default().to_string(),
+            "instructions: n/a · cycles: n/a · IPC: n/a · cache misses: n/a · branch misses: n/a"
+        );
+    }
+
+    #[test]
+    fn measures_or_degrades() {
+        let session = Session::start();
+        let sum: u64 = (0..100_000u64).map(std::hint::black_box).sum();
+        let counters = session.stop();
+        assert_eq!(sum, 4_999_950_000);
+        // counters are not available everywhere, but if they are, they counted something.
+        if let Some(instructions) = counters.instructions {
+            assert!(instructions > 0);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+/// Normalization of puzzle inputs before they reach a solution.
+///
+/// Inputs saved on Windows or pasted from a browser may start with a byte order mark, use `\r\n`
+/// line endings or end in blank lines. Solutions shouldn't have to care, so [`normalize`] turns
+/// all of them into plain `\n`-separated text.
+use std::env;
+
+/// How [`normalize`] treats line breaks at the end of the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingNewline {
+    /// Leave them as they are.
+    Keep,
+    /// End non-empty inputs with exactly one `\n`.
+    #[default]
+    Single,
+    /// Remove all of them.
+    Strip,
+}
+
+impl TrailingNewline {
+    /// Reads the policy from `AOC_TRAILING_NEWLINE` (`keep`, `single` or `strip`), falling back
+    /// to the default if it is unset or invalid.
+    #[must_use]
+    pub fn from_env() -> Self {
+        match env::var("AOC_TRAILING_NEWLINE").as_deref() {
+            Ok("keep") => Self::Keep,
+            Ok("strip") => Self::Strip,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Strips a leading byte order mark, converts `\r\n` and lone `\r` line endings to `\n` and
+/// applies the `trailing` newline policy.
+#[must_use]
+pub fn normalize(input: &str, trailing: TrailingNewline) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut res = input.replace("\r\n", "\n").replace('\r', "\n");
+
+    match trailing {
+        TrailingNewline::Keep => {}
+        TrailingNewline::Single | TrailingNewline::Strip => {
+            res.truncate(res.trim_end_matches('\n').len());
+            if trailing == TrailingNewline::Single && !res.is_empty() {
+                res.push('\n');
+            }
+        }
+    }
+
+    res
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize, TrailingNewline};
+
+    #[test]
+    fn strips_bom_and_line_endings() {
+        let input = "\u{feff}Time: 7 15\r\nDistance: 9 40\r\n";
+        assert_eq!(
+            normalize(input, TrailingNewline::Keep),
+            "Time: 7 15\nDistance: 9 40\n"
+        );
+        assert_eq!(normalize("a\rb\r\n", TrailingNewline::Keep), "a\nb\n");
+        // a BOM is only stripped at the very start.
+        assert_eq!(normalize("a\u{feff}", TrailingNewline::Keep), "a\u{feff}");
+    }
+
+    #[test]
+    fn applies_trailing_newline_policy() {
+        let input = "467..114..\n...*......\n\n\n";
+        assert_eq!(normalize(input, TrailingNewline::Keep), input);
+        assert_eq!(
+            normalize(input, TrailingNewline::Single),
+            "467..114..\n...*......\n"
+        );
+        assert_eq!(
+            normalize(input, TrailingNewline::Strip),
+            "467..114..\n...*......"
+        );
+        assert_eq!(
+            normalize("no newline", TrailingNewline::Single),
+            "no newline\n"
+        );
+        assert_eq!(normalize("\r\n\r\n", TrailingNewline::Single), "");
+    }
+
+    #[test]
+    fn keeps_blank_lines_between_sections() {
+        let input = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 2\r\n";
+        assert_eq!(
+            normalize(input, TrailingNewline::Single),
+            "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n"
+        );
+    }
+}
@@ -1,48 +1,85 @@
 use crate::Day;
-use std::{env, fs};
+use input::TrailingNewline;
+use std::fs;
 
+pub mod alloc;
+pub mod answers;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod counters;
+pub mod input;
+pub mod progress;
+pub mod puzzle;
+pub mod readme;
 pub mod readme_benchmarks;
+pub mod release;
+pub mod report;
 pub mod runner;
+pub mod snapshot;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// The folder is looked up in the data layout of the [`config`], e.g. `inputs`.
+/// The contents are normalized with [`input::normalize`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = config::get().data.folder(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::normalize(
+        &f.expect("could not open input file"),
+        TrailingNewline::from_env(),
+    )
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
+    let filepath = config::get()
+        .data
+        .folder(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::normalize(
+        &f.expect("could not open input file"),
+        TrailingNewline::from_env(),
+    )
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// An optional `validate` hook, e.g. `solution!(3, validate = parse)`, is called with the input
+/// before solving. It returns a [`crate::parse::Result`] and stops the run with a clear message
+/// if the input violates the structural assumptions of the solution.
+///
+/// An optional `graph` hook, e.g. `solution!(8, validate = parse, graph = graph)`, builds a
+/// [`crate::viz::dot::Graph`] of the input for `cargo explore <day> --dot <path>`.
+///
+/// With the `memory` feature, the binary's global allocator is [`alloc::PeakAlloc`], which
+/// measures the peak memory of parts.
+/// Its arguments are parsed into [`runner::Args`], see `--help` of a day's binary.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr $(, validate = $validate:path)? $(, graph = $graph:path)? $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[cfg(feature = "memory")]
+        #[global_allocator]
+        static ALLOC: advent_of_code::template::alloc::PeakAlloc =
+            advent_of_code::template::alloc::PeakAlloc;
+
         fn main() {
             use advent_of_code::template::runner::*;
+            let args = Args::from_env();
             let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            $(check_input(|| $validate(&input).map(drop), DAY);)?
+            explore(None $(.or(Some($graph as fn(&str) -> _)))?, &input, DAY, args.dot.as_deref());
+            run_part(part_one, &input, DAY, 1, &args.options);
+            run_part(part_two, &input, DAY, 2, &args.options);
         }
     };
 }
@@ -0,0 +1,288 @@
+/// Progress through the calendar, computed from local state only.
+///
+/// A part counts as solved if its solution returns `Some`, and as starred if the answer store
+/// (see [`answers`](super::answers)) has a confirmed answer for it.
+use std::fmt::Write as _;
+
+use crate::template::{release, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// The default name of the readme block that holds the stars table.
+pub const BLOCK: &str = "stars";
+
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GRAY: &str = "\x1b[90m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// There is no solution for the day yet.
+    Missing,
+    /// The solution returns `None`, or could not run.
+    Unsolved,
+    /// The solution returns an answer that was not confirmed yet.
+    Solved,
+    /// The solution returns the confirmed answer, or could not run to check it.
+    Starred,
+    /// The solution returns something other than the confirmed answer.
+    Regressed,
+}
+
+impl Status {
+    /// Computes the status of a part from the `result` of its solution, `None` if the solution
+    /// could not run, and its `confirmed` answer.
+    #[must_use]
+    pub fn of(result: Option<Option<&str>>, confirmed: Option<&str>) -> Self {
+        match (result, confirmed) {
+            (None, None) | (Some(None), None) => Status::Unsolved,
+            (Some(Some(_)), None) => Status::Solved,
+            (None, Some(_)) => Status::Starred,
+            (Some(result), Some(confirmed)) if result == Some(confirmed) => Status::Starred,
+            (Some(_), Some(_)) => Status::Regressed,
+        }
+    }
+
+    /// Whether the star of the part was earned. A regressed part still has its star.
+    #[must_use]
+    pub fn is_earned(self) -> bool {
+        matches!(self, Status::Starred | Status::Regressed)
+    }
+
+    fn symbol(self) -> String {
+        match self {
+            Status::Missing => " ".into(),
+            Status::Unsolved => format!("{ANSI_GRAY}·{ANSI_RESET}"),
+            Status::Solved => "☆".into(),
+            Status::Starred => format!("{ANSI_YELLOW}★{ANSI_RESET}"),
+            Status::Regressed => format!("{ANSI_RED}★{ANSI_RESET}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub parts: [Status; 2],
+}
+
+impl DayProgress {
+    #[must_use]
+    pub fn missing(day: Day) -> Self {
+        Self {
+            day,
+            parts: [Status::Missing; 2],
+        }
+    }
+
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.parts.iter().filter(|p| p.is_earned()).count()
+    }
+}
+
+/// Extracts the answers of both parts from the output of a solution binary.
+#[must_use]
+pub fn parse_results(output: &[String]) -> [Option<String>; 2] {
+    let mut results = [None, None];
+
+    for (i, line) in output.iter().enumerate() {
+        // intermediate results are overwritten with a carriage return.
+        let line = line.rsplit('\r').next().unwrap_or_default();
+        let Some((part, rest)) = line.split_once(": ") else {
+            continue;
+        };
+        let slot = match part {
+            "Part 1" => &mut results[0],
+            "Part 2" => &mut results[1],
+            _ => continue,
+        };
+
+        if rest.starts_with('▼') {
+            let answer: Vec<&str> = output[i + 1..]
+                .iter()
+                .map(String::as_str)
+                .take_while(|l| !l.starts_with("Part ") && !l.starts_with('\r'))
+                .collect();
+            *slot = Some(answer.join("\n"));
+        } else if let Some(answer) = rest.strip_prefix(ANSI_BOLD) {
+            *slot = answer.split(ANSI_RESET).next().map(str::to_string);
+        }
+    }
+
+    results
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders December of `year` as a calendar with the stars of every day.
+#[must_use]
+pub fn calendar(progress: &[DayProgress], year: u16) -> String {
+    let stars: usize = progress.iter().map(DayProgress::stars).sum();
+    let mut out = format!(
+        "{ANSI_BOLD}December {year}{ANSI_RESET}{:>24}{ANSI_YELLOW}★{ANSI_RESET} {stars}/50\n",
+        ""
+    );
+    out.push_str(" Mon   Tue   Wed   Thu   Fri   Sat   Sun\n");
+
+    let offset = weekday(year, 12, 1);
+    let mut grid = "      ".repeat(offset);
+    for (i, day) in all_days().enumerate() {
+        let day_progress = progress
+            .iter()
+            .find(|p| p.day == day)
+            .copied()
+            .unwrap_or_else(|| DayProgress::missing(day));
+        let [one, two] = day_progress.parts.map(Status::symbol);
+
+        if day_progress.parts == [Status::Missing; 2] {
+            let _ = write!(grid, "{ANSI_GRAY}{:>2}{ANSI_RESET}   ", day.into_inner());
+        } else {
+            let _ = write!(grid, "{:>2} {one}{two}", day.into_inner());
+        }
+
+        if (offset + i + 1).is_multiple_of(7) {
+            grid.push('\n');
+        } else {
+            grid.push(' ');
+        }
+    }
+
+    for line in grid.lines() {
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    let _ = writeln!(
+        out,
+        "\n{} confirmed  {} not submitted  {} changed since  {} unsolved",
+        Status::Starred.symbol(),
+        Status::Solved.symbol(),
+        Status::Regressed.symbol(),
+        Status::Unsolved.symbol(),
+    );
+    out
+}
+
+/// Renders a markdown table with the stars of every scaffolded day, for the readme.
+#[must_use]
+pub fn stars_table(progress: &[DayProgress], year: u16) -> String {
+    let stars: usize = progress.iter().map(DayProgress::stars).sum();
+    let mut lines: Vec<String> = vec![
+        "## ⭐️ Progress".into(),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for p in progress.iter().filter(|p| p.parts != [Status::Missing; 2]) {
+        let [one, two] = p
+            .parts
+            .map(|status| if status.is_earned() { "⭐" } else { " " });
+        let url = release::puzzle_url(year, p.day);
+        let day = p.day.into_inner();
+        lines.push(format!("| [Day {day}]({url}) | {one} | {two} |"));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+    lines.join("\n")
+}
+
+/// The day of the week, `0` for Monday.
+fn weekday(year: u16, month: u16, day: u16) -> usize {
+    // Sakamoto's method, which counts from Sunday.
+    const OFFSETS: [u16; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let sunday_based =
+        (year + year / 4 - year / 100 + year / 400 + OFFSETS[month as usize - 1] + day) % 7;
+    (usize::from(sunday_based) + 6) % 7
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{calendar, parse_results, stars_table, weekday, DayProgress, Status};
+    use crate::day;
+
+    fn progress() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                day: day!(1),
+                parts: [Status::Starred, Status::Solved],
+            },
+            DayProgress {
+                day: day!(2),
+                parts: [Status::Regressed, Status::Unsolved],
+            },
+        ]
+    }
+
+    #[test]
+    fn computes_status() {
+        assert_eq!(Status::of(Some(Some("42")), Some("42")), Status::Starred);
+        assert_eq!(Status::of(Some(Some("41")), Some("42")), Status::Regressed);
+        assert_eq!(Status::of(Some(None), Some("42")), Status::Regressed);
+        assert_eq!(Status::of(None, Some("42")), Status::Starred);
+        assert_eq!(Status::of(Some(Some("42")), None), Status::Solved);
+        assert_eq!(Status::of(Some(None), None), Status::Unsolved);
+        assert_eq!(Status::of(None, None), Status::Unsolved);
+    }
+
+    #[test]
+    fn parses_results() {
+        let output = [
+            "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.0µs)",
+            "Part 2: ▼ \r",
+            "Part 2: ▼  (2.0µs)",
+            "#.#",
+            ".#.",
+        ]
+        .map(String::from);
+        assert_eq!(
+            parse_results(&output),
+            [Some("42".into()), Some("#.#\n.#.".into())]
+        );
+
+        let output = ["Part 1: ✖\rPart 1: ✖             ".into()];
+        assert_eq!(parse_results(&output), [None, None]);
+    }
+
+    #[test]
+    fn computes_weekdays() {
+        // December 1st, 2023 was a Friday, 2024 a Sunday.
+        assert_eq!(weekday(2023, 12, 1), 4);
+        assert_eq!(weekday(2024, 12, 1), 6);
+        assert_eq!(weekday(2024, 2, 29), 3);
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let calendar = calendar(&progress(), 2023);
+        let lines: Vec<&str> = calendar.lines().collect();
+        assert!(lines[0].ends_with("★\x1b[0m 2/50"));
+        // day 1 is a friday, in the fifth column.
+        assert!(lines[2].starts_with(&format!("{} 1 ", " ".repeat(24))));
+        assert!(lines[2].contains(" 1 \x1b[33m★\x1b[0m☆  2 \x1b[31m★\x1b[0m\x1b[90m·\x1b[0m"));
+        // 2 + 7 + 7 + 7 + 2 days over five weeks.
+        assert_eq!(lines.len(), 9);
+    }
+
+    #[test]
+    fn renders_stars_table() {
+        let mut progress = progress();
+        progress.push(DayProgress::missing(day!(3)));
+        assert_eq!(
+            stars_table(&progress, 2023),
+            [
+                "## ⭐️ Progress",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---: |",
+                "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ |   |",
+                "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+                "",
+                "**Stars: 2/50**",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,554 @@
+/// Renders the puzzle descriptions that aoc-cli stores as markdown, e.g. `data/puzzles/01.md`.
+///
+/// Only the markdown that aoc-cli writes is understood: headings, paragraphs, lists and fenced
+/// code blocks, with `*emphasis*`, `` `code` ``, links and the `<em>` tags of the puzzle page.
+/// The blocks after the `--- Part Two ---` heading belong to part 2.
+use std::fmt::Write as _;
+
+use super::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+/// The indentation of code blocks.
+const CODE_INDENT: &str = "    ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Heading(String),
+    Paragraph(String),
+    /// An item of a list.
+    Item(String),
+    /// The lines of a fenced code block, without the fences.
+    Code(String),
+}
+
+/// A code block of the description, usually an example input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// The position of the block among all code blocks, starting at 1.
+    pub index: usize,
+    pub part: u8,
+    /// The text of the block without markup.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The blocks with the part they belong to.
+    blocks: Vec<(u8, Block)>,
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let mut part = 1;
+        let blocks = parse_blocks(markdown)
+            .into_iter()
+            .map(|block| {
+                if matches!(&block, Block::Heading(text) if text.contains("Part Two")) {
+                    part = 2;
+                }
+                (part, block)
+            })
+            .collect();
+        Self { blocks }
+    }
+
+    /// Whether the description contains `part`. Part 2 is only in descriptions that were
+    /// downloaded after solving part 1.
+    #[must_use]
+    pub fn has_part(&self, part: u8) -> bool {
+        self.blocks.iter().any(|(p, _)| *p == part)
+    }
+
+    /// The blocks of `part`, or of both parts.
+    pub fn blocks(&self, part: Option<u8>) -> impl Iterator<Item = &Block> {
+        self.blocks
+            .iter()
+            .filter(move |(p, _)| part.is_none_or(|part| *p == part))
+            .map(|(_, block)| block)
+    }
+
+    /// The code blocks of `part`, or of both parts. Indices count the code blocks of both parts,
+    /// so an example has the same index with and without the filter.
+    #[must_use]
+    pub fn examples(&self, part: Option<u8>) -> Vec<Example> {
+        self.blocks
+            .iter()
+            .filter_map(|(p, block)| match block {
+                Block::Code(code) => Some((*p, code)),
+                _ => None,
+            })
+            .enumerate()
+            .filter(|(_, (p, _))| part.is_none_or(|part| *p == part))
+            .map(|(i, (p, code))| Example {
+                index: i + 1,
+                part: p,
+                text: plain(&code_spans(code)),
+            })
+            .collect()
+    }
+
+    /// Renders the blocks of `part`, or of both parts, as text that is wrapped at `width`
+    /// characters. With `color`, headings, emphasis and code are highlighted with ANSI colors.
+    #[must_use]
+    pub fn render(&self, part: Option<u8>, width: usize, color: bool) -> String {
+        let mut out = String::new();
+        let mut previous: Option<&Block> = None;
+
+        for block in self.blocks(part) {
+            let list = matches!(previous, Some(Block::Item(_))) && matches!(block, Block::Item(_));
+            if previous.is_some() && !list {
+                out.push('\n');
+            }
+
+            match block {
+                Block::Heading(text) => {
+                    let text = plain(&spans(text));
+                    if color {
+                        let _ = writeln!(out, "{ANSI_BOLD}{ANSI_GREEN}{text}{ANSI_RESET}");
+                    } else {
+                        let _ = writeln!(out, "{text}");
+                    }
+                }
+                Block::Paragraph(text) => wrap(&mut out, &spans(text), width, "", "", color),
+                Block::Item(text) => wrap(&mut out, &spans(text), width, "  • ", "    ", color),
+                Block::Code(code) => {
+                    for line in code.lines() {
+                        out.push_str(CODE_INDENT);
+                        for (text, style) in code_spans(line) {
+                            out.push_str(&style.apply(&text, color));
+                        }
+                        out.push('\n');
+                    }
+                }
+            }
+            previous = Some(block);
+        }
+
+        out
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            blocks.extend(current.take());
+            let mut code = String::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push_str(line);
+                code.push('\n');
+            }
+            blocks.push(Block::Code(code.trim_end_matches('\n').to_string()));
+        } else if trimmed.is_empty() {
+            blocks.extend(current.take());
+        } else if is_underline(trimmed) {
+            // the underline of a heading, or a horizontal rule if there is no text above it.
+            if let Some(Block::Paragraph(text)) = current.take() {
+                blocks.push(Block::Heading(text));
+            }
+        } else if trimmed.starts_with('#') {
+            blocks.extend(current.take());
+            blocks.push(Block::Heading(
+                trimmed.trim_start_matches('#').trim().to_string(),
+            ));
+        } else if let Some(item) = ["* ", "- ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            blocks.extend(current.take());
+            current = Some(Block::Item(item.trim().to_string()));
+        } else {
+            match &mut current {
+                Some(Block::Paragraph(text) | Block::Item(text)) => {
+                    text.push(' ');
+                    text.push_str(trimmed);
+                }
+                _ => current = Some(Block::Paragraph(trimmed.to_string())),
+            }
+        }
+    }
+
+    blocks.extend(current);
+    blocks
+}
+
+fn is_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.bytes().all(|b| b == b'-') || line.bytes().all(|b| b == b'='))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    em: bool,
+    code: bool,
+}
+
+impl Style {
+    fn apply(self, text: &str, color: bool) -> String {
+        match self {
+            _ if !color => text.to_string(),
+            Style { em: true, .. } => format!("{ANSI_BOLD}{ANSI_YELLOW}{text}{ANSI_RESET}"),
+            Style { code: true, .. } => format!("{ANSI_CYAN}{text}{ANSI_RESET}"),
+            Style { .. } => text.to_string(),
+        }
+    }
+}
+
+type Spans = Vec<(String, Style)>;
+
+/// The styled runs of text of a paragraph.
+fn spans(text: &str) -> Spans {
+    let mut out = Vec::new();
+    markup(text, Style::default(), true, &mut out);
+    out
+}
+
+/// The styled runs of text of a line of a code block, where only tags and entities are markup.
+fn code_spans(line: &str) -> Spans {
+    let mut out = Vec::new();
+    let style = Style {
+        em: false,
+        code: true,
+    };
+    markup(line, style, false, &mut out);
+    out
+}
+
+fn plain(spans: &Spans) -> String {
+    spans.iter().map(|(text, _)| text.as_str()).collect()
+}
+
+fn markup(text: &str, mut style: Style, markdown: bool, out: &mut Spans) {
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        rest = after;
+
+        match c {
+            '\\' if markdown && !after.is_empty() => {
+                let escaped = after.chars().next().unwrap();
+                push(out, escaped, style);
+                rest = &after[escaped.len_utf8()..];
+            }
+            '`' if markdown && after.contains('`') => {
+                let end = after.find('`').unwrap();
+                let code = &after[..end];
+                // aoc-cli writes `<code><em>42</em></code>` as `*42*` in backticks.
+                let (code, em) = match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                    Some(inner) if !inner.is_empty() => (inner, true),
+                    _ => (code, style.em),
+                };
+                for c in code.chars() {
+                    push(out, c, Style { em, code: true });
+                }
+                rest = &after[end + 1..];
+            }
+            '*' if markdown => {
+                style.em = !style.em;
+                rest = after.trim_start_matches('*');
+            }
+            '[' if markdown => match link(after) {
+                Some((label, len)) => {
+                    markup(label, style, markdown, out);
+                    rest = &after[len..];
+                }
+                None => push(out, c, style),
+            },
+            '<' => match tag(after) {
+                Some((name, closing, len)) => {
+                    match name {
+                        "em" => style.em = !closing,
+                        "code" => style.code = !closing,
+                        _ => {}
+                    }
+                    rest = &after[len..];
+                }
+                None => push(out, c, style),
+            },
+            '&' => match entity(after) {
+                Some((decoded, len)) => {
+                    push(out, decoded, style);
+                    rest = &after[len..];
+                }
+                None => push(out, c, style),
+            },
+            c => push(out, c, style),
+        }
+    }
+}
+
+fn push(out: &mut Spans, c: char, style: Style) {
+    match out.last_mut() {
+        Some((text, last)) if *last == style => text.push(c),
+        _ => out.push((c.to_string(), style)),
+    }
+}
+
+/// The label of a `[label](url)` link that follows an opening bracket, and the length of the
+/// rest of the link. Brackets that are not directly followed by `(url)` are not a link.
+fn link(after: &str) -> Option<(&str, usize)> {
+    let close = after.find(']')?;
+    let url = after[close + 1..].strip_prefix('(')?;
+    let end = url.find(')')?;
+    Some((&after[..close], close + 2 + end + 1))
+}
+
+/// The name of a HTML tag that follows a `<`, whether it closes an element, and the length of
+/// the rest of the tag.
+fn tag(after: &str) -> Option<(&str, bool, usize)> {
+    let end = after.find('>')?;
+    let inner = &after[..end];
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let name = inner
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some((name, closing, end + 1))
+}
+
+/// The character of a HTML entity that follows a `&`, and the length of the rest of the entity.
+fn entity(after: &str) -> Option<(char, usize)> {
+    let end = after.find(';').filter(|&end| end <= 6)?;
+    let c = match &after[..end] {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" | "#39" => '\'',
+        "nbsp" => ' ',
+        _ => return None,
+    };
+    Some((c, end + 1))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Appends the words of `spans` to `out` in lines of at most `width` characters, unless a word
+/// is longer. The first line starts with `first`, the others with `indent`.
+fn wrap(out: &mut String, spans: &Spans, width: usize, first: &str, indent: &str, color: bool) {
+    out.push_str(first);
+    let mut len = first.chars().count();
+    let mut line_start = true;
+
+    for word in words(spans) {
+        let word_len: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+        if !line_start && len + 1 + word_len > width {
+            out.push('\n');
+            out.push_str(indent);
+            len = indent.chars().count();
+            line_start = true;
+        }
+        if !line_start {
+            out.push(' ');
+            len += 1;
+        }
+        for (text, style) in &word {
+            out.push_str(&style.apply(text, color));
+        }
+        len += word_len;
+        line_start = false;
+    }
+
+    out.push('\n');
+}
+
+/// The whitespace-separated words of `spans`, each made of the styled runs in it.
+fn words(spans: &Spans) -> Vec<Spans> {
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+
+    for (text, style) in spans {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            } else {
+                push(&mut word, c, *style);
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Block, Example, Puzzle};
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---
+----------
+
+You are *bored*. Read the [calibration document](/2023/day/1)
+on the page.
+
+For example:
+
+```
+1abc2
+a<em>1</em>b&lt;
+```
+
+The values are `12` and `38`. Adding these together produces `*142*`.
+
+Your puzzle answer was `54388`.
+
+\\--- Part Two ---
+----------
+
+Some digits are spelled out:
+
+* `one`, `two` and `three`
+* `four`
+
+```
+two1nine
+```
+
+They provide two gold stars: \\*\\*
+";
+
+    #[test]
+    fn parses_blocks_and_parts() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(
+            puzzle.blocks(Some(1)).take(3).collect::<Vec<_>>(),
+            [
+                &Block::Heading("\\--- Day 1: Trebuchet?! ---".into()),
+                &Block::Paragraph(
+                    "You are *bored*. Read the [calibration document](/2023/day/1) on the page."
+                        .into()
+                ),
+                &Block::Paragraph("For example:".into()),
+            ]
+        );
+        assert_eq!(puzzle.blocks(Some(1)).count(), 6);
+        assert_eq!(
+            puzzle.blocks(Some(2)).collect::<Vec<_>>(),
+            [
+                &Block::Heading("\\--- Part Two ---".into()),
+                &Block::Paragraph("Some digits are spelled out:".into()),
+                &Block::Item("`one`, `two` and `three`".into()),
+                &Block::Item("`four`".into()),
+                &Block::Code("two1nine".into()),
+                &Block::Paragraph("They provide two gold stars: \\*\\*".into()),
+            ]
+        );
+        assert!(puzzle.has_part(2));
+        assert!(!Puzzle::parse("\\--- Day 1 ---\n---\n\nText.").has_part(2));
+    }
+
+    #[test]
+    fn lists_examples() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(
+            puzzle.examples(None),
+            [
+                Example {
+                    index: 1,
+                    part: 1,
+                    text: "1abc2\na1b<".into()
+                },
+                Example {
+                    index: 2,
+                    part: 2,
+                    text: "two1nine".into()
+                }
+            ]
+        );
+        assert_eq!(puzzle.examples(Some(2))[0].index, 2);
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(
+            puzzle.render(Some(1), 30, false),
+            "--- Day 1: Trebuchet?! ---\n\
+             \n\
+             You are bored. Read the\n\
+             calibration document on the\n\
+             page.\n\
+             \n\
+             For example:\n\
+             \n\
+             \x20   1abc2\n\
+             \x20   a1b<\n\
+             \n\
+             The values are 12 and 38.\n\
+             Adding these together produces\n\
+             142.\n\
+             \n\
+             Your puzzle answer was 54388.\n"
+        );
+        assert_eq!(
+            puzzle.render(Some(2), 30, false),
+            "--- Part Two ---\n\
+             \n\
+             Some digits are spelled out:\n\
+             \n\
+             \x20 • one, two and three\n\
+             \x20 • four\n\
+             \n\
+             \x20   two1nine\n\
+             \n\
+             They provide two gold stars:\n\
+             **\n"
+        );
+    }
+
+    #[test]
+    fn highlights_emphasis_and_code() {
+        let puzzle =
+            Puzzle::parse("# Day 1\n\nIt is *`142`*, `*7*` or `x`.\n\n```\na<em>1</em>\n```");
+
+        assert_eq!(
+            puzzle.render(None, 80, true),
+            "\x1b[1m\x1b[32mDay 1\x1b[0m\n\
+             \n\
+             It is \x1b[1m\x1b[33m142\x1b[0m, \x1b[1m\x1b[33m7\x1b[0m or \x1b[36mx\x1b[0m.\n\
+             \n\
+             \x20   \x1b[36ma\x1b[0m\x1b[1m\x1b[33m1\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn renders_link_labels() {
+        let puzzle = Puzzle::parse("# Day 1\n\nSee [a] b [c](https://example.com) and [d] (e).");
+
+        assert_eq!(
+            puzzle.render(None, 80, false),
+            "Day 1\n\nSee [a] b c and [d] (e).\n"
+        );
+    }
+}
@@ -0,0 +1,287 @@
+/// Named blocks in the readme that commands regenerate.
+///
+/// A block starts with an opening tag like `<!-- aoc:progress year=2023 -->` and ends with the
+/// matching `<!-- /aoc:progress -->`. Everything in between belongs to the command that owns the
+/// block and is replaced whenever it regenerates it. Blocks are updated independently of each
+/// other, so the readme can contain any of them in any order.
+///
+/// Tags only count at the start of a line, so the readme can mention them in running text. A
+/// closing tag may also follow its opening tag directly, for an empty block.
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::path::Path;
+use std::{fs, io};
+
+const OPEN: &str = "<!-- aoc:";
+const CLOSE: &str = "<!-- /aoc:";
+const END: &str = "-->";
+
+#[derive(Debug)]
+pub enum Error {
+    /// A block opened at the byte offset is never closed.
+    Unclosed(String, usize),
+    /// A closing tag at the byte offset has no opening tag.
+    Unopened(String, usize),
+    /// A block appears more than once.
+    Duplicate(String),
+    /// A block opened at the byte offset contains another block.
+    Nested(String, usize),
+    /// A tag at the byte offset is missing its `-->`.
+    Malformed(usize),
+    /// The readme does not contain the block.
+    Missing(String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unclosed(name, pos) => write!(
+                f,
+                "block `{name}` opened at byte {pos} is not closed with `{CLOSE}{name} {END}`"
+            ),
+            Error::Unopened(name, pos) => {
+                write!(
+                    f,
+                    "closing tag of block `{name}` at byte {pos} has no opening tag"
+                )
+            }
+            Error::Duplicate(name) => write!(f, "block `{name}` appears more than once"),
+            Error::Nested(name, pos) => {
+                write!(
+                    f,
+                    "block `{name}` opened at byte {pos} contains another block"
+                )
+            }
+            Error::Malformed(pos) => write!(f, "tag at byte {pos} is missing its `{END}`"),
+            Error::Missing(name) => write!(
+                f,
+                "no `{OPEN}{name} {END}` ... `{CLOSE}{name} {END}` block found"
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// A block and the attributes of its opening tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    pub name: &'a str,
+    pub attributes: Vec<(&'a str, &'a str)>,
+    /// The byte range between the opening and the closing tag.
+    content: Range<usize>,
+}
+
+impl Block<'_> {
+    /// The value of the attribute `key`, e.g. `2023` for `year` in `<!-- aoc:progress year=2023 -->`.
+    #[must_use]
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find_map(|&(k, v)| (k == key).then_some(v))
+    }
+}
+
+/// Whether [`update`] rewrites the readme or only compares it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Write,
+    /// Leaves the readme as it is, for CI.
+    Check,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Unchanged,
+    Updated,
+    /// The block differs, but was not rewritten in [`Mode::Check`].
+    Stale,
+}
+
+/// Finds all blocks of `readme`, in order.
+pub fn blocks(readme: &str) -> Result<Vec<Block<'_>>, Error> {
+    let mut blocks: Vec<Block> = vec![];
+    let mut pos = 0;
+
+    while let Some(offset) = readme[pos..].find("<!-- ") {
+        let start = pos + offset;
+        let rest = &readme[start..];
+        if !is_line_start(readme, start) {
+            pos = start + 1;
+            continue;
+        }
+
+        if let Some(tag) = rest.strip_prefix(CLOSE) {
+            let name = tag.split(END).next().unwrap_or_default().trim();
+            return Err(Error::Unopened(name.to_string(), start));
+        }
+        let Some(tag) = rest.strip_prefix(OPEN) else {
+            pos = start + 1;
+            continue;
+        };
+
+        let tag_len = tag.find(END).ok_or(Error::Malformed(start))?;
+        let mut words = tag[..tag_len].split_whitespace();
+        let name = words.next().ok_or(Error::Malformed(start))?;
+        let attributes = words.filter_map(|word| word.split_once('=')).collect();
+
+        let content_start = start + OPEN.len() + tag_len + END.len();
+        let closing = format!("{CLOSE}{name} {END}");
+        let content_end = readme[content_start..]
+            .match_indices(&closing)
+            .map(|(i, _)| content_start + i)
+            .find(|&i| i == content_start || is_line_start(readme, i))
+            .ok_or_else(|| Error::Unclosed(name.to_string(), start))?;
+        let content = content_start..content_end;
+
+        if readme[content.clone()]
+            .lines()
+            .any(|line| line.starts_with(OPEN))
+        {
+            return Err(Error::Nested(name.to_string(), start));
+        }
+        if blocks.iter().any(|b| b.name == name) {
+            return Err(Error::Duplicate(name.to_string()));
+        }
+
+        pos = content.end + closing.len();
+        blocks.push(Block {
+            name,
+            attributes,
+            content,
+        });
+    }
+
+    Ok(blocks)
+}
+
+fn is_line_start(readme: &str, pos: usize) -> bool {
+    pos == 0 || readme[..pos].ends_with('\n')
+}
+
+/// Replaces the content of the block `name` with what `render` returns for it.
+pub fn replace(
+    readme: &str,
+    name: &str,
+    render: impl FnOnce(&Block) -> String,
+) -> Result<String, Error> {
+    let blocks = blocks(readme)?;
+    let block = blocks
+        .iter()
+        .find(|b| b.name == name)
+        .ok_or_else(|| Error::Missing(name.to_string()))?;
+
+    let content = render(block);
+    let mut out = readme.to_string();
+    out.replace_range(
+        block.content.clone(),
+        &format!("\n{}\n", content.trim_end()),
+    );
+    Ok(out)
+}
+
+/// Regenerates the block `name` of the readme at `path`, see [`replace`].
+pub fn update(
+    path: impl AsRef<Path>,
+    name: &str,
+    mode: Mode,
+    render: impl FnOnce(&Block) -> String,
+) -> Result<Status, Error> {
+    rewrite(path, mode, |readme| replace(readme, name, render))
+}
+
+/// Rewrites the readme at `path` with `edit`, for updates that need more than [`replace`].
+pub fn rewrite(
+    path: impl AsRef<Path>,
+    mode: Mode,
+    edit: impl FnOnce(&str) -> Result<String, Error>,
+) -> Result<Status, Error> {
+    let path = path.as_ref();
+    let readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let updated = edit(&readme)?;
+
+    Ok(match (updated == readme, mode) {
+        (true, _) => Status::Unchanged,
+        (false, Mode::Check) => Status::Stale,
+        (false, Mode::Write) => {
+            fs::write(path, updated)?;
+            Status::Updated
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, replace, Error};
+
+    const README: &str = "# readme\n\
+        <!-- aoc:stars -->\nold stars\n<!-- /aoc:stars -->\n\
+        text <!-- a comment --> and `<!-- aoc:stars -->`\n\
+        <!-- aoc:progress year=2023 theme=dark -->\n<!-- /aoc:progress -->\n";
+
+    #[test]
+    fn finds_blocks_and_attributes() {
+        let blocks = blocks(README).unwrap();
+        let names: Vec<_> = blocks.iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["stars", "progress"]);
+        assert_eq!(blocks[1].attribute("year"), Some("2023"));
+        assert_eq!(blocks[1].attribute("theme"), Some("dark"));
+        assert_eq!(blocks[0].attribute("year"), None);
+    }
+
+    #[test]
+    fn replaces_blocks_independently() {
+        let readme = replace(README, "progress", |block| {
+            format!("progress of {}", block.attribute("year").unwrap())
+        })
+        .unwrap();
+        assert_eq!(
+            readme,
+            "# readme\n\
+             <!-- aoc:stars -->\nold stars\n<!-- /aoc:stars -->\n\
+             text <!-- a comment --> and `<!-- aoc:stars -->`\n\
+             <!-- aoc:progress year=2023 theme=dark -->\nprogress of 2023\n<!-- /aoc:progress -->\n"
+        );
+        // regenerating is idempotent.
+        let again = replace(&readme, "progress", |_| "progress of 2023\n".into()).unwrap();
+        assert_eq!(again, readme);
+    }
+
+    #[test]
+    fn reports_broken_blocks() {
+        let err = |readme: &str| blocks(readme).unwrap_err().to_string();
+        assert_eq!(
+            err("<!-- aoc:stars -->"),
+            "block `stars` opened at byte 0 is not closed with `<!-- /aoc:stars -->`"
+        );
+        assert_eq!(
+            err("<!-- aoc:stars --> <!-- /aoc:stars -->"),
+            "block `stars` opened at byte 0 is not closed with `<!-- /aoc:stars -->`"
+        );
+        assert_eq!(
+            err("x\n<!-- /aoc:stars -->"),
+            "closing tag of block `stars` at byte 2 has no opening tag"
+        );
+        assert_eq!(
+            err("<!-- aoc:a --><!-- /aoc:a -->\n<!-- aoc:a -->\n<!-- /aoc:a -->"),
+            "block `a` appears more than once"
+        );
+        assert_eq!(
+            err("<!-- aoc:a -->\n<!-- aoc:b --><!-- /aoc:b -->\n<!-- /aoc:a -->"),
+            "block `a` opened at byte 0 contains another block"
+        );
+        assert_eq!(err("<!-- aoc:a "), "tag at byte 0 is missing its `-->`");
+        assert!(matches!(
+            replace("# readme", "stars", |_| String::new()),
+            Err(Error::Missing(name)) if name == "stars"
+        ));
+    }
+}
@@ -1,22 +1,18 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the `benchmarks` block of the readme with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::time::Duration;
 
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::config::{BenchmarkTable, Column, Config, Sort};
+use crate::template::readme::{self, Mode, Status};
 use crate::Day;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// The default name of the readme block that holds the table.
+pub const BLOCK: &str = "benchmarks";
 
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
+/// The marker that enclosed the table before it became a readme block. The table started and
+/// ended with it, and an empty table was a single marker.
+const LEGACY_MARKER: &str = "<!--- benchmarking table --->";
 
 #[derive(Clone)]
 pub struct Timings {
@@ -24,150 +20,280 @@ pub struct Timings {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// The average time of each part.
+    pub part_nanos: [Option<f64>; 2],
+    /// Measurements of each part, if the binary saved its samples.
+    pub stats: [Option<PartStats>; 2],
+    /// The duration of the `validate` hook, which parses the input.
+    pub parse_nanos: Option<f64>,
+    /// The total time of the day at the baseline commit.
+    pub baseline_nanos: Option<f64>,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// Statistics over the benchmark samples of a part.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartStats {
+    pub samples: usize,
+    pub stddev_nanos: f64,
+    /// The peak heap memory in bytes.
+    pub peak_memory: Option<u64>,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl PartStats {
+    #[must_use]
+    pub fn from_samples(nanos: &[u64], peak_memory: Option<u64>) -> Option<Self> {
+        if nanos.is_empty() {
+            return None;
+        }
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().map(|&x| x as f64).sum::<f64>() / n;
+        let variance = nanos
+            .iter()
+            .map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        Some(Self {
+            samples: nanos.len(),
+            stddev_nanos: variance.sqrt(),
+            peak_memory,
+        })
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn construct_table(
+    prefix: &str,
+    mut timings: Vec<Timings>,
+    total_millis: f64,
+    config: &BenchmarkTable,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    let mut header_row = "| Day | Part 1 | Part 2 |".to_string();
+    let mut align_row = "| :---: | :---: | :---:  |".to_string();
+    for column in &config.columns {
+        header_row.push_str(&format!(" {} |", column_header(*column, config)));
+        align_row.push_str(" :---: |");
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    let mut lines: Vec<String> = vec![header, String::new(), header_row, align_row];
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
-
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    if config.sort == Sort::Slowest {
+        timings.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
 
-    for timing in timings {
+    for timing in &timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut row = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        );
+        for column in &config.columns {
+            row.push_str(&format!(" {} |", cell(*column, timing, total_millis)));
+        }
+        lines.push(row);
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    let mut total = format!("**Total: {total_millis:.2}ms**");
+    if config.part_totals {
+        let [one, two] = [0, 1]
+            .map(|i| timings.iter().filter_map(|t| t.part_nanos[i]).sum::<f64>() / 1_000_000_f64);
+        total.push_str(&format!(" (part 1: {one:.2}ms, part 2: {two:.2}ms)"));
+    }
+    lines.push(total);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn column_header(column: Column, config: &BenchmarkTable) -> String {
+    match column {
+        Column::Samples => "Samples".into(),
+        Column::Stddev => "Std. dev.".into(),
+        Column::Memory => "Peak memory".into(),
+        Column::Parse => "Parse".into(),
+        Column::Total => "Total".into(),
+        Column::Percent => "% of total".into(),
+        Column::Speedup => match &config.baseline {
+            Some(commit) => format!("Speedup vs `{commit}`"),
+            None => "Speedup".into(),
+        },
+    }
+}
+
+fn cell(column: Column, timing: &Timings, total_millis: f64) -> String {
+    let per_part = |f: &dyn Fn(&PartStats) -> Option<String>| {
+        let [one, two] = [&timing.stats[0], &timing.stats[1]]
+            .map(|stats| stats.as_ref().and_then(f).map_or("-".into(), code));
+        format!("{one} / {two}")
+    };
+
+    match column {
+        Column::Samples => per_part(&|s| Some(s.samples.to_string())),
+        Column::Stddev => per_part(&|s| Some(format!("±{}", duration(s.stddev_nanos)))),
+        Column::Memory => per_part(&|s| s.peak_memory.map(bytes)),
+        Column::Parse => timing.parse_nanos.map_or("-".into(), |n| code(duration(n))),
+        Column::Total => code(duration(timing.total_nanos)),
+        Column::Percent => code(format!(
+            "{:.1}%",
+            100.0 * timing.total_nanos / (total_millis * 1_000_000.0).max(f64::EPSILON)
+        )),
+        Column::Speedup => timing.baseline_nanos.map_or("-".into(), |baseline| {
+            code(format!("{:.2}×", baseline / timing.total_nanos.max(1.0)))
+        }),
+    }
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+fn code(s: String) -> String {
+    format!("`{s}`")
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn duration(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn bytes(bytes: u64) -> String {
+    match bytes {
+        0..=999 => format!("{bytes} B"),
+        1_000..=999_999 => format!("{:.1} kB", bytes as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1} MB", bytes as f64 / 1e6),
+        _ => format!("{:.1} GB", bytes as f64 / 1e9),
+    }
+}
+
+/// Regenerates the benchmarks block of the readme, or only checks it in [`Mode::Check`].
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    config: &Config,
+    mode: Mode,
+) -> Result<Status, readme::Error> {
+    let readme = &config.readme;
+    readme::rewrite(&readme.path, mode, |s| {
+        replace(s, &readme.blocks.benchmarks, || {
+            construct_table("##", timings, total_millis, &config.benchmarks)
+        })
+    })
+}
+
+/// Replaces the content of the block `name` with the table, after turning a table between
+/// [`LEGACY_MARKER`]s into that block if the readme does not have it yet.
+fn replace(
+    readme: &str,
+    name: &str,
+    table: impl FnOnce() -> String,
+) -> Result<String, readme::Error> {
+    let has_block =
+        readme::blocks(readme).is_ok_and(|blocks| blocks.iter().any(|b| b.name == name));
+    let readme = match (readme.find(LEGACY_MARKER), readme.rfind(LEGACY_MARKER)) {
+        (Some(start), Some(end)) if !has_block => {
+            let mut migrated = readme.to_string();
+            migrated.replace_range(
+                start..end + LEGACY_MARKER.len(),
+                &format!("<!-- aoc:{name} -->\n<!-- /aoc:{name} -->"),
+            );
+            migrated
+        }
+        _ => readme.to_string(),
+    };
+    readme::replace(&readme, name, |_| table())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{construct_table, replace, PartStats, Timings, BLOCK};
     use crate::day;
+    use crate::template::config::{BenchmarkTable, Column, Sort};
+    use crate::template::readme;
+    use crate::Day;
+
+    fn update_content(
+        s: &str,
+        timings: Vec<Timings>,
+        total_millis: f64,
+    ) -> Result<String, readme::Error> {
+        let config = BenchmarkTable::default();
+        replace(s, BLOCK, || {
+            construct_table("##", timings, total_millis, &config)
+        })
+    }
+
+    fn timing(day: Day, part_1_ms: u32, part_2_ms: u32) -> Timings {
+        let part_nanos = [part_1_ms, part_2_ms].map(|ms| f64::from(ms) * 1e6);
+        Timings {
+            day,
+            part_1: Some(format!("{part_1_ms}ms")),
+            part_2: Some(format!("{part_2_ms}ms")),
+            total_nanos: part_nanos[0] + part_nanos[1],
+            part_nanos: part_nanos.map(Some),
+            stats: [None, None],
+            parse_nanos: None,
+            baseline_nanos: None,
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
-            Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
-            },
-            Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
-            },
-            Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
-            },
+            timing(day!(1), 10, 20),
+            timing(day!(2), 30, 40),
+            timing(day!(4), 40, 50),
         ]
     }
 
+    const EMPTY: &str = "foo\nbar\n<!-- aoc:benchmarks --><!-- /aoc:benchmarks -->\nbaz";
+
     #[test]
     #[should_panic]
-    fn errors_if_marker_not_present() {
-        let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+    fn errors_if_block_not_present() {
+        update_content("# readme", get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
-    fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+    fn errors_if_block_not_closed() {
+        let s = "<!-- aoc:benchmarks --> <!-- aoc:benchmarks -->";
+        update_content(s, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let s = update_content(EMPTY, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        let s = update_content(EMPTY, get_mock_timings(), 190.0).unwrap();
+        let s = update_content(&s, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches("<!-- aoc:benchmarks -->").count(), 1);
+        assert_eq!(s.matches("<!-- /aoc:benchmarks -->").count(), 1);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn migrates_legacy_markers() {
+        let updated = update_content(EMPTY, get_mock_timings(), 190.0).unwrap();
+        let empty = "foo\nbar\n<!--- benchmarking table --->\nbaz";
+        let old_table = "foo\nbar\n<!--- benchmarking table --->\n## Benchmarks\n\n\
+            | Day | Part 1 | Part 2 |\n| :---: | :---: | :---:  |\n\
+            | [Day 3](./src/bin/03.rs) | `1ms` | `2ms` |\n\n\
+            **Total: 3.00ms**\n<!--- benchmarking table --->\nbaz";
+        for legacy in [empty, old_table] {
+            let s = update_content(legacy, get_mock_timings(), 190.0).unwrap();
+            assert_eq!(s, updated);
+        }
+    }
+
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let s = update_content(EMPTY, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
+            "<!-- aoc:benchmarks -->",
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
@@ -177,10 +303,52 @@ mod tests {
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!-- /aoc:benchmarks -->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].stats = [
+            PartStats::from_samples(&[9_000_000, 11_000_000], Some(2_500)),
+            PartStats::from_samples(&[20_000_000], Some(1_200_000)),
+        ];
+        timings[0].parse_nanos = Some(1_500.0);
+        timings[0].baseline_nanos = Some(60e6);
+        timings[1].part_2 = None;
+        timings[1].part_nanos[1] = None;
+        timings[1].total_nanos = 30e6;
+
+        let config = BenchmarkTable {
+            columns: vec![
+                Column::Samples,
+                Column::Stddev,
+                Column::Memory,
+                Column::Parse,
+                Column::Total,
+                Column::Percent,
+                Column::Speedup,
+            ],
+            sort: Sort::Slowest,
+            part_totals: true,
+            baseline: Some("abc1234".into()),
+        };
+        let expected = [
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Samples | Std. dev. | Peak memory | Parse | Total | % of total | Speedup vs `abc1234` |",
+            "| :---: | :---: | :---:  | :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | - / - | - / - | - / - | - | `90.0ms` | `60.0%` | - |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2` / `1` | `±1.0ms` / `±0.0ns` | `2.5 kB` / `1.2 MB` | `1.5µs` | `30.0ms` | `20.0%` | `2.00×` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `-` | - / - | - / - | - / - | - | `30.0ms` | `20.0%` | - |",
+            "",
+            "**Total: 150.00ms** (part 1: 80.00ms, part 2: 70.00ms)",
+        ]
+        .join("\n");
+        assert_eq!(construct_table("##", timings, 150.0, &config), expected);
+    }
 }
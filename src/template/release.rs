@@ -0,0 +1,192 @@
+/// The release schedule of the puzzles.
+///
+/// A new puzzle unlocks every day from December 1st to 25th at midnight in UTC-5, the timezone of
+/// the Advent of Code servers. The date of "today" is computed in that timezone, so the puzzle of
+/// the day changes at 05:00 UTC everywhere.
+use std::fmt::{self, Display};
+
+use crate::Day;
+
+/// The offset of the release timezone from UTC.
+const RELEASE_OFFSET_SECS: i64 = -5 * 60 * 60;
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// The date in the release timezone at `unix_secs` seconds since the Unix epoch.
+    #[must_use]
+    pub fn at(unix_secs: i64) -> Self {
+        let days = (unix_secs + RELEASE_OFFSET_SECS).div_euclid(SECS_PER_DAY);
+        civil_from_days(days)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The first puzzle of the year is still `days` days away.
+    NotStarted { year: u16, days: i64 },
+    /// The last puzzle of the year was released already.
+    Over { year: u16 },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotStarted { year, days: 1 } => write!(
+                f,
+                "Advent of Code {year} has not started yet, the first puzzle unlocks tonight at midnight UTC-5."
+            ),
+            Error::NotStarted { year, days } => write!(
+                f,
+                "Advent of Code {year} has not started yet, the first puzzle unlocks in {days} days."
+            ),
+            Error::Over { year } => write!(
+                f,
+                "All puzzles of Advent of Code {year} are out, there is no new one today. Scaffold a day with `cargo scaffold <day>`."
+            ),
+        }
+    }
+}
+
+/// The day whose puzzle unlocked last, if `unix_secs` falls between the releases of the first and
+/// the last puzzle of `year`.
+pub fn puzzle_day(unix_secs: i64, year: u16) -> Result<Day, Error> {
+    let today = Date::at(unix_secs);
+    let first = days_from_civil(i64::from(year), 12, 1);
+    let days = days_from_civil(today.year, today.month, today.day) - first;
+
+    match u8::try_from(days + 1).ok().and_then(Day::new) {
+        Some(day) => Ok(day),
+        None if days < 0 => Err(Error::NotStarted { year, days: -days }),
+        None => Err(Error::Over { year }),
+    }
+}
+
+/// The puzzle page of `day`.
+#[must_use]
+pub fn puzzle_url(year: u16, day: Day) -> String {
+    format!("https://adventofcode.com/{year}/day/{}", day.into_inner())
+}
+
+/* -------------------------------------------------------------------------- */
+
+// Howard Hinnant's algorithms for the proleptic Gregorian calendar, with eras of 400 years that
+// start on March 1st so the leap day is the last day of a year.
+
+fn civil_from_days(days: i64) -> Date {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Date {
+        year,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_day, puzzle_url, Date, Error};
+    use crate::day;
+
+    /// 2023-12-01 00:00:00 UTC.
+    const DEC_1_2023: i64 = 1_701_388_800;
+    const HOUR: i64 = 60 * 60;
+    const DAY: i64 = 24 * HOUR;
+
+    fn date(year: i64, month: u8, day: u8) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn computes_dates_in_release_timezone() {
+        assert_eq!(Date::at(0), date(1969, 12, 31));
+        assert_eq!(Date::at(5 * HOUR), date(1970, 1, 1));
+        assert_eq!(Date::at(DEC_1_2023), date(2023, 11, 30));
+        assert_eq!(Date::at(DEC_1_2023 + 5 * HOUR), date(2023, 12, 1));
+        // 2024-02-29 12:00 UTC and 2000-03-01 04:59 UTC.
+        assert_eq!(Date::at(1_709_208_000), date(2024, 2, 29));
+        assert_eq!(Date::at(951_886_740), date(2000, 2, 29));
+        assert_eq!(Date::at(-DAY), date(1969, 12, 30));
+    }
+
+    #[test]
+    fn finds_puzzle_day_at_release() {
+        let release = DEC_1_2023 + 5 * HOUR;
+        assert_eq!(puzzle_day(release, 2023), Ok(day!(1)));
+        assert_eq!(puzzle_day(release + DAY - 1, 2023), Ok(day!(1)));
+        assert_eq!(puzzle_day(release + 7 * DAY, 2023), Ok(day!(8)));
+        assert_eq!(
+            puzzle_day(release + 24 * DAY + 23 * HOUR, 2023),
+            Ok(day!(25))
+        );
+    }
+
+    #[test]
+    fn refuses_days_outside_of_advent() {
+        let release = DEC_1_2023 + 5 * HOUR;
+        assert_eq!(
+            puzzle_day(release - 1, 2023),
+            Err(Error::NotStarted {
+                year: 2023,
+                days: 1
+            })
+        );
+        assert_eq!(
+            puzzle_day(release - 30 * DAY, 2023),
+            Err(Error::NotStarted {
+                year: 2023,
+                days: 30
+            })
+        );
+        assert_eq!(
+            puzzle_day(release + 25 * DAY, 2023),
+            Err(Error::Over { year: 2023 })
+        );
+        assert_eq!(puzzle_day(release, 2022), Err(Error::Over { year: 2022 }));
+        assert!(matches!(
+            puzzle_day(release, 2024),
+            Err(Error::NotStarted { year: 2024, .. })
+        ));
+    }
+
+    #[test]
+    fn links_puzzles() {
+        assert_eq!(
+            puzzle_url(2023, day!(8)),
+            "https://adventofcode.com/2023/day/8"
+        );
+    }
+}
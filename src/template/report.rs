@@ -0,0 +1,510 @@
+/// Renders benchmark samples as a self-contained HTML report.
+///
+/// Charts are inline SVG and styles are inline CSS, so the page works offline and without
+/// scripts. Times are drawn on log scales, since days range from nanoseconds to seconds.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::viz::{Color, BACKGROUND, FOREGROUND};
+use crate::Day;
+
+const WIDTH: f64 = 760.0;
+const LABEL_WIDTH: f64 = 90.0;
+const ROW_HEIGHT: f64 = 22.0;
+const AXIS_HEIGHT: f64 = 30.0;
+const PART_COLORS: [Color; 2] = [Color::Green, Color::Yellow];
+const DAY_COLORS: [Color; 6] = [
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Red,
+];
+
+/// The benchmark samples of one part, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Samples {
+    pub day: Day,
+    pub part: u8,
+    pub nanos: Vec<u64>,
+}
+
+impl Samples {
+    /// The `q`-quantile of the samples, e.g. `0.5` for the median.
+    #[must_use]
+    pub fn quantile(&self, q: f64) -> u64 {
+        let mut sorted = self.nanos.clone();
+        sorted.sort_unstable();
+        let i = ((sorted.len().saturating_sub(1)) as f64 * q).round() as usize;
+        sorted.get(i).copied().unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn median(&self) -> u64 {
+        self.quantile(0.5)
+    }
+}
+
+/// The median time of a part at a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub commit: String,
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub median_nanos: u64,
+}
+
+/// Parses the `commit,timestamp,day,part,median_nanos` lines of a history file, skipping the
+/// header and lines that don't parse.
+#[must_use]
+pub fn parse_history(csv: &str) -> Vec<HistoryEntry> {
+    csv.lines()
+        .filter_map(|line| {
+            let mut fields = line.split(',');
+            let entry = HistoryEntry {
+                commit: fields.next()?.to_string(),
+                timestamp: fields.next()?.parse().ok()?,
+                day: fields.next()?.parse().ok()?,
+                part: fields.next()?.parse().ok()?,
+                median_nanos: fields.next()?.parse().ok()?,
+            };
+            fields.next().is_none().then_some(entry)
+        })
+        .collect()
+}
+
+/// Writes a history file, see [`parse_history`].
+#[must_use]
+pub fn format_history(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("commit,timestamp,day,part,median_nanos\n");
+    for e in entries {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            e.commit, e.timestamp, e.day, e.part, e.median_nanos
+        );
+    }
+    out
+}
+
+/// Renders the report page for `samples`, with a history chart if `history` spans more than
+/// one commit.
+#[must_use]
+pub fn render(samples: &[Samples], history: &[HistoryEntry], title: &str) -> String {
+    let total: u64 = samples.iter().map(Samples::median).sum();
+    let [br, bg, bb] = BACKGROUND;
+    let [fr, fg, fb] = FOREGROUND;
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n\
+         body{{background:#{br:02x}{bg:02x}{bb:02x};color:#{fr:02x}{fg:02x}{fb:02x};\
+         font-family:monospace;max-width:{WIDTH}px;margin:2em auto;padding:0 1em}}\n\
+         h1,h2{{color:#{g}}}\nsvg{{display:block;margin:1em 0}}\n\
+         svg text{{fill:#{fr:02x}{fg:02x}{fb:02x};font-size:12px;font-family:monospace}}\n\
+         .legend span{{margin-right:1.5em}}\n</style>\n</head>\n<body>\n",
+        title = escape(title),
+        g = &Color::Green.hex()[1..],
+    );
+    let _ = writeln!(out, "<h1>{}</h1>", escape(title));
+
+    if samples.is_empty() {
+        out.push_str("<p>No benchmarks found. Solve a day first.</p>\n</body>\n</html>\n");
+        return out;
+    }
+
+    let _ = writeln!(out, "<p>Total of the medians: {}</p>", format_nanos(total));
+    out.push_str(&legend(&[
+        ("Part 1", PART_COLORS[0]),
+        ("Part 2", PART_COLORS[1]),
+    ]));
+
+    out.push_str("<h2>Median time per day</h2>\n");
+    out.push_str(&median_chart(samples));
+    out.push_str("<h2>Part 1 vs part 2</h2>\n");
+    out.push_str("<p>How many times slower part 2 is than part 1, on a log scale.</p>\n");
+    out.push_str(&comparison_chart(samples));
+    out.push_str("<h2>Sample distribution</h2>\n");
+    out.push_str(
+        "<p>Whiskers span the 5th to 95th percentile, boxes the 25th to 75th, \
+         with a line at the median.</p>\n",
+    );
+    out.push_str(&distribution_chart(samples));
+    out.push_str("<h2>History</h2>\n");
+    out.push_str(&history_chart(history));
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps times onto `[LABEL_WIDTH, WIDTH]` on a log scale that covers whole powers of ten.
+struct LogScale {
+    lo: f64,
+    hi: f64,
+}
+
+impl LogScale {
+    fn new(values: impl Iterator<Item = u64>) -> Self {
+        let (mut lo, mut hi) = (f64::MAX, f64::MIN);
+        for v in values {
+            let v = (v.max(1) as f64).log10();
+            lo = lo.min(v.floor());
+            hi = hi.max(v.ceil());
+        }
+        if lo > hi {
+            (lo, hi) = (0.0, 1.0);
+        }
+        if hi <= lo {
+            hi = lo + 1.0;
+        }
+        Self { lo, hi }
+    }
+
+    fn x(&self, nanos: u64) -> f64 {
+        let v = (nanos.max(1) as f64).log10();
+        LABEL_WIDTH + (v - self.lo) / (self.hi - self.lo) * (WIDTH - LABEL_WIDTH - 10.0)
+    }
+
+    /// Tick marks with labels at every power of ten, from `top` down to `bottom`.
+    fn axis(&self, top: f64, bottom: f64) -> String {
+        let mut out = String::new();
+        for exp in self.lo as i32..=self.hi as i32 {
+            let nanos = 10u64.pow(exp.max(0) as u32);
+            let x = self.x(nanos);
+            let _ = writeln!(
+                out,
+                "<line x1=\"{x:.1}\" y1=\"{top}\" x2=\"{x:.1}\" y2=\"{bottom}\" stroke=\"#333\"/>\
+                 <text x=\"{x:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{:?}</text>",
+                bottom + 15.0,
+                Duration::from_nanos(nanos)
+            );
+        }
+        out
+    }
+}
+
+fn svg(height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" \
+         viewBox=\"0 0 {WIDTH} {height}\">\n{body}</svg>\n"
+    )
+}
+
+fn days(samples: &[Samples]) -> Vec<Day> {
+    let mut days: Vec<Day> = samples.iter().map(|s| s.day).collect();
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+fn part(samples: &[Samples], day: Day, part: u8) -> Option<&Samples> {
+    samples
+        .iter()
+        .find(|s| s.day == day && s.part == part && !s.nanos.is_empty())
+}
+
+fn median_chart(samples: &[Samples]) -> String {
+    let scale = LogScale::new(samples.iter().map(Samples::median));
+    let days = days(samples);
+    let height = days.len() as f64 * 2.0 * ROW_HEIGHT;
+
+    let mut body = scale.axis(0.0, height);
+    for (i, &day) in days.iter().enumerate() {
+        let y = i as f64 * 2.0 * ROW_HEIGHT;
+        let _ = writeln!(
+            body,
+            "<text x=\"0\" y=\"{:.1}\">Day {day}</text>",
+            y + ROW_HEIGHT + 4.0
+        );
+        for p in 1..=2 {
+            let Some(s) = part(samples, day, p) else {
+                continue;
+            };
+            let median = s.median();
+            let bar_y = y + f64::from(p - 1) * (ROW_HEIGHT - 2.0) + 2.0;
+            let _ = writeln!(
+                body,
+                "<rect x=\"{LABEL_WIDTH}\" y=\"{bar_y:.1}\" width=\"{:.1}\" height=\"{}\" \
+                 fill=\"{}\"><title>Day {day} part {p}: {}</title></rect>",
+                (scale.x(median) - LABEL_WIDTH).max(1.0),
+                ROW_HEIGHT - 4.0,
+                PART_COLORS[p as usize - 1].hex(),
+                format_nanos(median)
+            );
+        }
+    }
+    svg(height + AXIS_HEIGHT, &body)
+}
+
+fn comparison_chart(samples: &[Samples]) -> String {
+    let rows: Vec<(Day, f64)> = days(samples)
+        .into_iter()
+        .filter_map(|day| {
+            let (p1, p2) = (part(samples, day, 1)?, part(samples, day, 2)?);
+            Some((day, p2.median().max(1) as f64 / p1.median().max(1) as f64))
+        })
+        .collect();
+    if rows.is_empty() {
+        return "<p>No day has timings for both parts.</p>\n".to_string();
+    }
+
+    let max = rows
+        .iter()
+        .map(|(_, ratio)| ratio.log10().abs())
+        .fold(1.0, f64::max)
+        .ceil();
+    let center = LABEL_WIDTH + (WIDTH - LABEL_WIDTH - 110.0) / 2.0;
+    let half = (WIDTH - LABEL_WIDTH - 110.0) / 2.0;
+    let height = rows.len() as f64 * ROW_HEIGHT;
+
+    let mut body = format!(
+        "<line x1=\"{center}\" y1=\"0\" x2=\"{center}\" y2=\"{height}\" stroke=\"#666\"/>\n"
+    );
+    for (i, (day, ratio)) in rows.iter().enumerate() {
+        let y = i as f64 * ROW_HEIGHT;
+        let w = ratio.log10() / max * half;
+        let (x, color) = if w >= 0.0 {
+            (center, PART_COLORS[1])
+        } else {
+            (center + w, PART_COLORS[0])
+        };
+        let _ = writeln!(
+            body,
+            "<text x=\"0\" y=\"{:.1}\">Day {day}</text>\
+             <rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"/>\
+             <text x=\"{:.1}\" y=\"{:.1}\">{ratio:.2}×</text>",
+            y + 15.0,
+            y + 2.0,
+            w.abs().max(1.0),
+            ROW_HEIGHT - 4.0,
+            color.hex(),
+            WIDTH - 100.0,
+            y + 15.0,
+        );
+    }
+    svg(height, &body)
+}
+
+fn distribution_chart(samples: &[Samples]) -> String {
+    let rows: Vec<&Samples> = days(samples)
+        .into_iter()
+        .flat_map(|day| (1..=2).filter_map(move |p| part(samples, day, p)))
+        .collect();
+    let scale = LogScale::new(rows.iter().flat_map(|s| s.nanos.iter().copied()));
+    let height = rows.len() as f64 * ROW_HEIGHT;
+
+    let mut body = scale.axis(0.0, height);
+    for (i, s) in rows.iter().enumerate() {
+        let y = i as f64 * ROW_HEIGHT;
+        let mid = y + ROW_HEIGHT / 2.0;
+        let [p5, p25, p50, p75, p95] =
+            [0.05, 0.25, 0.5, 0.75, 0.95].map(|q| scale.x(s.quantile(q)));
+        let color = PART_COLORS[s.part as usize - 1].hex();
+        let _ = writeln!(
+            body,
+            "<text x=\"0\" y=\"{:.1}\">Day {} / {}</text>\
+             <line x1=\"{p5:.1}\" y1=\"{mid:.1}\" x2=\"{p95:.1}\" y2=\"{mid:.1}\" stroke=\"{color}\"/>\
+             <rect x=\"{p25:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{}\" fill=\"none\" stroke=\"{color}\">\
+             <title>Day {} part {}: median {} over {} samples</title></rect>\
+             <line x1=\"{p50:.1}\" y1=\"{:.1}\" x2=\"{p50:.1}\" y2=\"{:.1}\" stroke=\"{color}\" stroke-width=\"2\"/>",
+            mid + 4.0,
+            s.day,
+            s.part,
+            y + 4.0,
+            (p75 - p25).max(1.0),
+            ROW_HEIGHT - 8.0,
+            s.day,
+            s.part,
+            format_nanos(s.median()),
+            s.nanos.len(),
+            y + 3.0,
+            y + ROW_HEIGHT - 3.0,
+        );
+    }
+    svg(height + AXIS_HEIGHT, &body)
+}
+
+/// Total median time per day over commits, oldest first.
+fn history_chart(history: &[HistoryEntry]) -> String {
+    let mut commits: Vec<(u64, &str)> = history
+        .iter()
+        .map(|e| (e.timestamp, e.commit.as_str()))
+        .collect();
+    commits.sort_unstable();
+    commits.dedup_by_key(|(_, commit)| *commit);
+    if commits.len() < 2 {
+        return "<p>No timing history yet. Every <code>cargo timings</code> records the medians \
+                of the current commit in <code>data/timings/history.csv</code>.</p>\n"
+            .to_string();
+    }
+
+    let mut days: Vec<Day> = history.iter().map(|e| e.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let total = |day: Day, commit: &str| -> Option<u64> {
+        let parts = history
+            .iter()
+            .filter(|e| e.day == day && e.commit == commit);
+        parts.map(|e| e.median_nanos).reduce(|a, b| a + b)
+    };
+
+    // the chart is turned on its side: commits go down, times go right.
+    let scale = LogScale::new(
+        history.iter().map(|e| e.median_nanos).chain(
+            days.iter()
+                .flat_map(|&day| commits.iter().filter_map(move |(_, c)| total(day, c))),
+        ),
+    );
+    let height = commits.len() as f64 * ROW_HEIGHT;
+
+    let mut body = scale.axis(0.0, height);
+    for (i, (_, commit)) in commits.iter().enumerate() {
+        let _ = writeln!(
+            body,
+            "<text x=\"0\" y=\"{:.1}\">{}</text>",
+            i as f64 * ROW_HEIGHT + 15.0,
+            escape(commit)
+        );
+    }
+    let mut legend_items = vec![];
+    for (d, &day) in days.iter().enumerate() {
+        let color = DAY_COLORS[d % DAY_COLORS.len()];
+        let points: Vec<String> = commits
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (_, commit))| {
+                let nanos = total(day, commit)?;
+                Some(format!(
+                    "{:.1},{:.1}",
+                    scale.x(nanos),
+                    i as f64 * ROW_HEIGHT + 11.0
+                ))
+            })
+            .collect();
+        let _ = writeln!(
+            body,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+            points.join(" "),
+            color.hex()
+        );
+        legend_items.push((format!("Day {day}"), color));
+    }
+
+    let legend_items: Vec<(&str, Color)> = legend_items
+        .iter()
+        .map(|(label, color)| (label.as_str(), *color))
+        .collect();
+    legend(&legend_items) + &svg(height + AXIS_HEIGHT, &body)
+}
+
+fn legend(items: &[(&str, Color)]) -> String {
+    let items: Vec<String> = items
+        .iter()
+        .map(|(label, color)| {
+            format!(
+                "<span style=\"color:{}\">■ {}</span>",
+                color.hex(),
+                escape(label)
+            )
+        })
+        .collect();
+    format!("<p class=\"legend\">{}</p>\n", items.join(""))
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_history, parse_history, render, HistoryEntry, Samples};
+    use crate::day;
+
+    fn samples() -> Vec<Samples> {
+        vec![
+            Samples {
+                day: day!(1),
+                part: 1,
+                nanos: vec![300, 100, 200],
+            },
+            Samples {
+                day: day!(1),
+                part: 2,
+                nanos: vec![2_000, 1_000, 3_000],
+            },
+            Samples {
+                day: day!(2),
+                part: 1,
+                nanos: vec![5_000_000],
+            },
+        ]
+    }
+
+    fn entry(commit: &str, timestamp: u64, part: u8, median_nanos: u64) -> HistoryEntry {
+        HistoryEntry {
+            commit: commit.into(),
+            timestamp,
+            day: day!(1),
+            part,
+            median_nanos,
+        }
+    }
+
+    #[test]
+    fn computes_quantiles() {
+        let s = &samples()[0];
+        assert_eq!(s.median(), 200);
+        assert_eq!(s.quantile(0.0), 100);
+        assert_eq!(s.quantile(1.0), 300);
+    }
+
+    #[test]
+    fn round_trips_history() {
+        let entries = vec![entry("abc1234", 1, 1, 200), entry("abc1234", 1, 2, 2000)];
+        let csv = format_history(&entries);
+        assert!(csv.starts_with("commit,timestamp,day,part,median_nanos\n"));
+        assert_eq!(parse_history(&csv), entries);
+        assert_eq!(parse_history("garbage\n,,,\n"), vec![]);
+    }
+
+    #[test]
+    fn renders_self_contained_page() {
+        let history = [entry("aaa", 1, 1, 400), entry("bbb", 2, 1, 200)];
+        let html = render(&samples(), &history, "Benchmarks <2023>");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Benchmarks &lt;2023&gt;</title>"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("src="));
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.contains("Total of the medians: 5.0ms"));
+        // day 1: part 2 is ten times slower than part 1.
+        assert!(html.contains("10.00×"));
+        assert!(html.contains("Day 01 part 2: median 2.0µs over 3 samples"));
+        assert!(html.contains(">bbb</text>"));
+    }
+
+    #[test]
+    fn renders_without_history() {
+        let html = render(&samples(), &[entry("aaa", 1, 1, 400)], "Benchmarks");
+        assert!(html.contains("No timing history yet."));
+        assert_eq!(html.matches("<svg").count(), 3);
+        assert!(render(&[], &[], "Benchmarks").contains("No benchmarks found."));
+    }
+}
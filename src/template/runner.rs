@@ -1,65 +1,268 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
-use std::io::{stdout, Write};
+use crate::parse;
+use crate::template::answers::{self, Answer, ToAnswer};
+use crate::template::counters::{Counters, Session};
+use crate::template::{alloc, aoc_cli, config, ANSI_ITALIC, ANSI_RESET};
+use crate::viz::dot::Graph;
+use crate::{explain, profile, viz, Day};
+use clap::Parser;
+use std::ffi::OsString;
+use std::io::{stdin, stdout, Write};
+use std::path::{Path, PathBuf};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, fs, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The options of a day's binary. Commands that run it pass them on with [`RunOptions::to_args`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, clap::Args)]
+pub struct RunOptions {
+    /// Submit the answer of a part with aoc-cli.
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit: Option<u8>,
+    /// Benchmark both parts.
+    #[arg(long)]
+    pub time: bool,
+    /// Read hardware counters while benchmarking, implies `--time`.
+    #[arg(long)]
+    pub counters: bool,
+    /// Play back the frames of visualizations in the terminal.
+    #[arg(long)]
+    pub viz: bool,
+    /// Export the frames of visualizations to a .svg, .cast or .gif file.
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<PathBuf>,
+    /// Print the steps that the solution traced.
+    #[arg(long)]
+    pub explain: bool,
+    /// Print where the time of each part went, by `span!`.
+    #[arg(long)]
+    pub profile: bool,
+}
+
+impl RunOptions {
+    /// The arguments that the day's binary parses back into these options.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![];
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string().into()]);
+        }
+        let flags = [
+            (self.time, "--time"),
+            (self.counters, "--counters"),
+            (self.viz, "--viz"),
+            (self.explain, "--explain"),
+            (self.profile, "--profile"),
+        ];
+        args.extend(
+            flags
+                .iter()
+                .filter(|(on, _)| *on)
+                .map(|(_, flag)| flag.into()),
+        );
+        if let Some(path) = &self.export {
+            args.extend(["--export".into(), path.into()]);
+        }
+        args
+    }
+
+    fn is_timed(&self) -> bool {
+        self.time || self.counters
+    }
+}
+
+/// The arguments of a day's binary.
+#[derive(Debug, Parser)]
+#[command(about = "Runs both parts of the day's solution on its input")]
+pub struct Args {
+    #[command(flatten)]
+    pub options: RunOptions,
+    /// Write a graph of the input to a Graphviz file and exit, see `cargo explore`.
+    #[arg(long, value_name = "PATH")]
+    pub dot: Option<PathBuf>,
+}
+
+impl Args {
+    /// Parses the arguments of the day's binary and turns on the recorders that they ask for.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let args = Self::parse();
+        let options = &args.options;
+        viz::set_enabled(options.viz || options.export.is_some());
+        explain::set_enabled(options.explain);
+        profile::set_enabled(options.profile);
+        args
+    }
+}
+
+/// Runs the day's `validate` hook and exits with a description of the problem if the input failed it.
+/// The hook parses the input, so its duration is saved as the parse time of the day, see [`save_sample_file`].
+pub fn check_input(validate: impl FnOnce() -> parse::Result<()>, day: Day) {
+    let timer = Instant::now();
+    let result = validate();
+    let elapsed = timer.elapsed();
+
+    if let Err(e) = result {
+        eprintln!("Invalid input for day {day}: {e}");
+        process::exit(1);
+    }
+    save_sample_file(
+        &format!("{day}-parse.txt"),
+        &format!("{}\n", elapsed.as_nanos()),
+    );
+}
+
+/// Writes the graph of the input to `dot` and exits, if `--dot` was passed.
+pub fn explore(graph: Option<fn(&str) -> Graph>, input: &str, day: Day, dot: Option<&Path>) {
+    let Some(path) = dot else {
+        return;
+    };
+    let Some(graph) = graph else {
+        eprintln!("Day {day} has no graph to explore.");
+        process::exit(1);
+    };
+
+    let graph = graph(input);
+    if let Err(e) = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, graph.render()))
+    {
+        eprintln!("Failed to write graph: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "Wrote a graph with {} nodes and {} edges to \"{}\"",
+        graph.node_count(),
+        graph.edge_count(),
+        path.display()
+    );
+    for note in graph.notes() {
+        println!("  {note}");
+    }
+    process::exit(0);
+}
+
+pub fn run_part<I: Clone, T: ToAnswer>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let run = run_timed(func, input, part, options, |result| {
+        print_result(result.as_ref().map(T::to_answer).as_ref(), &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let answer = run.result.as_ref().map(T::to_answer);
+    print_result(
+        answer.as_ref(),
+        &part_str,
+        &format_duration(&run.duration, run.samples.len()),
+    );
+    if let Some(counters) = run.counters {
+        println!("  {ANSI_ITALIC}{counters}{ANSI_RESET}");
+    }
+
+    let samples: String = run
+        .samples
+        .iter()
+        .map(|sample| format!("{}\n", sample.as_nanos()))
+        .collect();
+    save_sample_file(&format!("{day}-{part}.txt"), &samples);
+    if let Some(peak) = run.peak_memory {
+        save_sample_file(&format!("{day}-{part}-memory.txt"), &format!("{peak}\n"));
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(answer) = answer {
+        if options.submit == Some(part) {
+            submit_result(&answer, day, part);
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer,
+///     see [`config::Sampling`].)
+///     With `--counters`, hardware counters are read around the benchmark and averaged per sample.
+///
+/// Frames emitted during the first execution are played back or exported if visualizations are turned on,
+/// steps traced during it are printed in explain mode and the timings of its spans with `--profile`.
+/// Its peak heap memory is measured as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: u8,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> Run<T> {
+    let (((((result, base_time), peak_memory), timings), frames), steps) = explain::record(|| {
+        viz::record(|| {
+            profile::record(|| {
+                alloc::measure(|| {
+                    let timer = Instant::now();
+                    let result = func(input.clone());
+                    (result, timer.elapsed())
+                })
+            })
+        })
+    });
 
+    viz::present(&frames, part, options.viz, options.export.as_deref());
+    print!("{}", explain::render(&steps, part));
+    print!("{}", profile::render(&timings, base_time, part));
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (samples, counters) = if options.is_timed() {
+        bench(func, input, &base_time, options.counters)
     } else {
-        (base_time, 1)
+        (vec![base_time], None)
     };
 
-    (result, run.0, run.1)
+    #[allow(clippy::cast_possible_truncation)]
+    let duration = Duration::from_nanos(average_duration(&samples) as u64);
+    Run {
+        result,
+        duration,
+        samples,
+        counters,
+        peak_memory,
+    }
+}
+
+/// The outcome of [`run_timed`].
+struct Run<T> {
+    result: T,
+    /// The average duration of all samples.
+    duration: Duration,
+    samples: Vec<Duration>,
+    counters: Option<Counters>,
+    /// The peak heap memory of the first execution in bytes.
+    peak_memory: Option<usize>,
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    with_counters: bool,
+) -> (Vec<Duration>, Option<Counters>) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = config::get().sampling.samples(base_time.as_nanos());
 
-    let mut timers: Vec<Duration> = vec![];
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
+    let session = with_counters.then(Session::start);
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -69,11 +272,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let counters = session.map(|session| session.stop().per_iteration(u64::from(bench_iterations)));
+
+    (timers, counters)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,7 +285,22 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// Writes measurements of the run to `$AOC_SAMPLES_DIR/<name>`, if that variable is set.
+/// `cargo all` and `cargo timings` read them for their tables and charts:
+///  - `<day>-<part>.txt`: the nanoseconds of every sample, one per line.
+///  - `<day>-<part>-memory.txt`: the peak heap memory of the part in bytes.
+///  - `<day>-parse.txt`: the nanoseconds that the `validate` hook took.
+fn save_sample_file(name: &str, content: &str) {
+    let Some(dir) = env::var_os("AOC_SAMPLES_DIR") else {
+        return;
+    };
+    let path = Path::new(&dir).join(name);
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, content)) {
+        eprintln!("Failed to write samples to \"{}\": {e}", path.display());
+    }
+}
+
+fn format_duration(duration: &Duration, samples: usize) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -92,22 +308,22 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(answer: Option<&Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match answer {
+        Some(answer) => {
+            if answer.is_multiline() {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
                     print!("\r");
                     println!("{str}");
-                    println!("{result}");
+                    println!("{answer}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -127,41 +343,95 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Try to submit one part of the solution if:
+///  1. aoc-cli is installed.
+///  2. the part has no confirmed answer yet, unless `resubmit` is set in `[submit]`.
+///  3. the answer fits on one line. Answers drawn in ASCII art have to be read and submitted by hand.
+///
+/// With `confirm` set in `[submit]`, the answer is only sent after a `y` on stdin.
+/// `cargo solve` only accepts `--submit` together with `--release`.
+fn submit_result(
+    answer: &Answer,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
 
-    if !args.contains(&"--submit".into()) {
+    if answer.is_multiline() {
+        println!("Part {part} spans several lines, submit the answer that it shows by hand.");
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let policy = &config::get().submit;
+    if let Some(confirmed) = answers::read(day, part).filter(|_| !policy.resubmit) {
+        println!(
+            "Part {part} already has the confirmed answer {confirmed}, not submitting. Set `resubmit = true` in `[submit]` to submit anyway."
+        );
+        return None;
+    }
+    if policy.confirm && !confirm_submit(part, answer) {
+        println!("Not submitting.");
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result via aoc-cli...");
+    let output = aoc_cli::submit(day, part, answer.as_str());
+    if output.as_ref().is_ok_and(aoc_cli::is_correct) {
+        match answers::record(day, part, answer) {
+            Ok(()) => println!(
+                "🎄 Recorded the answer in \"{}\".",
+                answers::path(day, part).display()
+            ),
+            Err(e) => eprintln!("Failed to record the answer: {e}"),
+        }
+    }
+    Some(output)
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+fn confirm_submit(part: u8, answer: &Answer) -> bool {
+    print!("Submit {answer} for part {part}? [y/N] ");
+    let _ = stdout().flush();
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
 
-    if part_submit != part {
-        return None;
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use clap::Parser;
+
+    use super::{Args, RunOptions};
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(std::iter::once("01").chain(args.iter().copied()))
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn passes_options_to_binary() {
+        let options = RunOptions {
+            submit: Some(2),
+            counters: true,
+            explain: true,
+            export: Some("out/01.svg".into()),
+            ..RunOptions::default()
+        };
+        let args = Args::try_parse_from(["01".into()].into_iter().chain(options.to_args()));
+        assert_eq!(args.unwrap().options, options);
+        assert!(RunOptions::default().to_args().is_empty());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn validates_arguments() {
+        assert_eq!(
+            parse(&["--dot", "x.dot"]).unwrap().dot,
+            Some("x.dot".into())
+        );
+        assert!(parse(&["--submit", "3"]).is_err());
+        assert!(parse(&["--submit"]).is_err());
+        assert!(parse(&["--times"]).is_err());
+    }
 }
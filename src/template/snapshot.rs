@@ -0,0 +1,201 @@
+/// Snapshot tests for answers that are compared as a whole, like multi-line ASCII art.
+///
+/// [`assert_snapshot`] compares the answer of a part with `data/snapshots/<day>-<part>.txt` (see
+/// [`config::Data`]) and panics with a line diff if they differ. With `AOC_BLESS` set, which
+/// `cargo snapshots --bless` does, the answer is written to the snapshot instead.
+use std::fmt::Write as _;
+use std::io::{stderr, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::answers::{Answer, ToAnswer};
+use crate::template::{config, ANSI_RESET};
+use crate::Day;
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+
+#[must_use]
+pub fn path(day: Day, part: u8) -> PathBuf {
+    config::get()
+        .data
+        .folder("snapshots")
+        .join(format!("{day}-{part}.txt"))
+}
+
+/// Compares the answer of a part with its snapshot, or updates the snapshot if `AOC_BLESS` is set.
+/// Both are compared as normalized [`Answer`]s, so line endings and trailing whitespace do not
+/// matter.
+///
+/// # Panics
+/// Panics if there is no answer, if the snapshot is missing or if it differs from the answer.
+#[track_caller]
+pub fn assert_snapshot<T: ToAnswer>(day: Day, part: u8, result: Option<T>) {
+    let Some(result) = result else {
+        panic!("day {day} part {part} has no answer to compare with its snapshot.");
+    };
+    let bless = env::var_os("AOC_BLESS").is_some();
+    let answer = result.to_answer();
+    if let Err(message) = check(&path(day, part), day, part, &answer, bless) {
+        panic!("{message}");
+    }
+}
+
+/// Compares `actual` with the snapshot at `path`, or writes it there with `bless`. The diff of a
+/// mismatch is colored if stderr is a terminal.
+fn check(path: &Path, day: Day, part: u8, actual: &Answer, bless: bool) -> Result<(), String> {
+    if bless {
+        return write(path, actual)
+            .map_err(|e| format!("failed to write snapshot \"{}\": {e}", path.display()));
+    }
+
+    let Ok(expected) = fs::read_to_string(path) else {
+        return Err(format!(
+            "there is no snapshot at \"{}\". Run `cargo snapshots {day} --bless` to create it.",
+            path.display()
+        ));
+    };
+    let expected = Answer::parse(&expected);
+    if &expected != actual {
+        return Err(format!(
+            "day {day} part {part} differs from the snapshot \"{}\" (- snapshot, + answer):\n{}\
+             Run `cargo snapshots {day} --bless` to update it.",
+            path.display(),
+            diff(expected.as_str(), actual.as_str(), stderr().is_terminal())
+        ));
+    }
+    Ok(())
+}
+
+fn write(path: &Path, snapshot: &Answer) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{snapshot}\n"))
+}
+
+/// A line diff of `expected` and `actual`: lines only in `expected` start with `-`, lines only in
+/// `actual` with `+`. With `color`, they are red and green.
+#[must_use]
+pub fn diff(expected: &str, actual: &str, color: bool) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lengths of the longest common subsequences of the suffixes, starting at `[i][j]`.
+    let mut common = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let mut line = |sign: char, text: &str, ansi: &str| {
+        if color && !ansi.is_empty() {
+            let _ = writeln!(out, "{ansi}{sign} {text}{ANSI_RESET}");
+        } else {
+            let _ = writeln!(out, "{sign} {text}");
+        }
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            line(' ', expected[i], "");
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            line('-', expected[i], ANSI_RED);
+            i += 1;
+        } else {
+            line('+', actual[j], ANSI_GREEN);
+            j += 1;
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    use super::{check, diff};
+    use crate::day;
+    use crate::template::answers::{Answer, ToAnswer};
+
+    /// A snapshot path in a fresh data folder below the system's temporary directory.
+    fn snapshot_path(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-snapshots-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("snapshots").join("10-1.txt")
+    }
+
+    #[test]
+    fn diffs_lines() {
+        assert_eq!(
+            diff("#..#\n####\n#..#", "#..#\n#..#\n#..#\n.##.", false),
+            "  #..#\n\
+             - ####\n\
+             \x20 #..#\n\
+             + #..#\n\
+             + .##.\n"
+        );
+        assert_eq!(diff("a\nb", "a\nb", false), "  a\n  b\n");
+        assert_eq!(diff("", "x", false), "+ x\n");
+    }
+
+    #[test]
+    fn colors_changed_lines() {
+        assert_eq!(
+            diff("a\nb", "a\nc", true),
+            "  a\n\x1b[31m- b\x1b[0m\n\x1b[32m+ c\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn blesses_and_compares_snapshots() {
+        let path = snapshot_path("bless");
+        let answer = Answer::parse("#..#\n####\n#..#");
+
+        assert_eq!(check(&path, day!(10), 1, &answer, true), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#..#\n####\n#..#\n");
+        assert_eq!(check(&path, day!(10), 1, &answer, false), Ok(()));
+
+        fs::write(&path, "#..#\r\n####  \r\n#..#\r\n\r\n").unwrap();
+        assert_eq!(check(&path, day!(10), 1, &answer, false), Ok(()));
+
+        let other = Answer::parse("#..#\n#..#");
+        let message = check(&path, day!(10), 1, &other, false).unwrap_err();
+        assert!(message.starts_with("day 10 part 1 differs from the snapshot"));
+        assert!(message.contains("- ####"));
+        assert!(message.ends_with("Run `cargo snapshots 10 --bless` to update it."));
+
+        assert_eq!(check(&path, day!(10), 1, &other, true), Ok(()));
+        assert_eq!(check(&path, day!(10), 1, &other, false), Ok(()));
+        let _ = fs::remove_dir_all(path.parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn reports_missing_snapshots() {
+        let path = snapshot_path("missing");
+        let message = check(&path, day!(10), 1, &Answer::from_int(42), false).unwrap_err();
+
+        assert_eq!(
+            message,
+            format!(
+                "there is no snapshot at \"{}\". Run `cargo snapshots 10 --bless` to create it.",
+                path.display()
+            )
+        );
+        assert!(!path.exists());
+    }
+}
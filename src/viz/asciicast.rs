@@ -0,0 +1,117 @@
+/// Renders frames as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) recording.
+///
+/// Each frame is written as one output event that clears the screen and prints the frame as
+/// [`super::terminal::render`] does, so `asciinema play` shows the same picture as `--viz`.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use super::terminal::{self, CLEAR_SCREEN};
+use super::Frame;
+
+/// Renders `frames` as an asciicast that shows each frame for `delay`.
+#[must_use]
+pub fn render(frames: &[Frame], delay: Duration) -> String {
+    let screens: Vec<String> = frames.iter().map(terminal::render).collect();
+    let width = screens
+        .iter()
+        .flat_map(|screen| screen.lines())
+        .map(visible_width)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let height = screens
+        .iter()
+        .map(|screen| screen.lines().count())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut out = format!("{{\"version\": 2, \"width\": {width}, \"height\": {height}}}\n");
+    let mut time = 0.0;
+    for screen in &screens {
+        let data = format!("{CLEAR_SCREEN}{}", screen.replace('\n', "\r\n"));
+        let _ = writeln!(out, "[{time:.3}, \"o\", \"{}\"]", escape(&data));
+        time += delay.as_secs_f64();
+    }
+    // an empty event at the end keeps the last frame on screen for its delay.
+    if !screens.is_empty() {
+        let _ = writeln!(out, "[{time:.3}, \"o\", \"\"]");
+    }
+
+    out
+}
+
+/// The number of characters in `line` that are not part of an ANSI escape sequence.
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip to the final byte of the sequence, e.g. the `m` of `\x1b[33m`.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Escapes `text` as the contents of a JSON string.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{escape, render, visible_width};
+    use crate::viz::{Color, Frame, Grid};
+
+    #[test]
+    fn renders_header_and_events() {
+        let mut grid = Grid::from_lines("1.\n.*");
+        grid.highlight(1, 1, Color::Yellow);
+        let frames = [
+            Frame::new("gears").with_grid(grid),
+            Frame::new("done").note("\"quoted\""),
+        ];
+        let cast = render(&frames, Duration::from_millis(250));
+        let lines: Vec<&str> = cast.lines().collect();
+
+        assert_eq!(lines[0], r#"{"version": 2, "width": 8, "height": 3}"#);
+        assert_eq!(
+            lines[1],
+            r#"[0.000, "o", "\u001b[2J\u001b[H\u001b[1mgears\u001b[0m\r\n1.\r\n.\u001b[33m\u001b[1m*\u001b[0m\r\n"]"#
+        );
+        assert!(lines[2].starts_with(r#"[0.250, "o", "#));
+        assert!(lines[2].contains(r#"\"quoted\""#));
+        assert_eq!(lines[3], r#"[0.500, "o", ""]"#);
+    }
+
+    #[test]
+    fn measures_and_escapes_text() {
+        assert_eq!(visible_width("\x1b[33m\x1b[1m*\x1b[0m."), 2);
+        assert_eq!(escape("a\\b\u{7}"), "a\\\\b\\u0007");
+    }
+}
@@ -0,0 +1,249 @@
+/// Exports graphs to [Graphviz](https://graphviz.org/) DOT.
+///
+/// [`Graph`] collects nodes and labeled edges from any adjacency structure. Nodes can be filled
+/// to highlight sets like starts and goals, and nodes and edges can be outlined to show structure
+/// like cycles. Render the output with e.g. `dot -Tsvg out.dot > out.svg`.
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+
+use super::Color;
+
+/// A directed graph that renders to DOT.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    name: String,
+    nodes: Vec<Node>,
+    index: HashMap<String, usize>,
+    edges: Vec<Edge>,
+    notes: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: String,
+    fill: Option<Color>,
+    outline: Option<Color>,
+}
+
+#[derive(Debug, Clone)]
+struct Edge {
+    from: usize,
+    to: usize,
+    label: String,
+    color: Option<Color>,
+}
+
+impl Graph {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Builds a graph from `(node, [(neighbor, edge label)])` entries.
+    #[must_use]
+    pub fn from_adjacency<N, L, E>(
+        name: impl Into<String>,
+        adjacency: impl IntoIterator<Item = (N, E)>,
+    ) -> Self
+    where
+        N: Display,
+        L: Display,
+        E: IntoIterator<Item = (N, L)>,
+    {
+        let mut graph = Self::new(name);
+        for (node, edges) in adjacency {
+            graph.node(&node);
+            for (to, label) in edges {
+                graph.edge(&node, to, label);
+            }
+        }
+        graph
+    }
+
+    /// Adds a node if it doesn't exist yet and returns its index.
+    pub fn node(&mut self, id: impl Display) -> usize {
+        let id = id.to_string();
+        if let Some(&i) = self.index.get(&id) {
+            return i;
+        }
+        self.index.insert(id.clone(), self.nodes.len());
+        self.nodes.push(Node {
+            id,
+            fill: None,
+            outline: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge, and its nodes if they don't exist yet. An empty `label` is left out.
+    pub fn edge(&mut self, from: impl Display, to: impl Display, label: impl Display) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges.push(Edge {
+            from,
+            to,
+            label: label.to_string(),
+            color: None,
+        });
+    }
+
+    /// Fills a node with `color`, e.g. to mark starts and goals.
+    pub fn highlight(&mut self, id: impl Display, color: Color) {
+        let i = self.node(id);
+        self.nodes[i].fill = Some(color);
+    }
+
+    /// Outlines a node with `color`, e.g. to mark the nodes of a cycle.
+    pub fn outline(&mut self, id: impl Display, color: Color) {
+        let i = self.node(id);
+        self.nodes[i].outline = Some(color);
+    }
+
+    /// Colors every edge from `from` to `to` that has the given `label`.
+    pub fn color_edge(&mut self, from: impl Display, to: impl Display, label: &str, color: Color) {
+        let (Some(&from), Some(&to)) = (
+            self.index.get(&from.to_string()),
+            self.index.get(&to.to_string()),
+        ) else {
+            return;
+        };
+        for edge in &mut self.edges {
+            if edge.from == from && edge.to == to && edge.label == label {
+                edge.color = Some(color);
+            }
+        }
+    }
+
+    /// Adds a line to the caption of the graph.
+    pub fn note(&mut self, note: impl Into<String>) {
+        self.notes.push(note.into());
+    }
+
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Renders the graph as a DOT `digraph`.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = format!("digraph {} {{\n", quote(&self.name));
+        out.push_str("    node [shape=circle fontname=monospace];\n");
+        out.push_str("    edge [fontname=monospace];\n");
+        if !self.notes.is_empty() {
+            let _ = writeln!(
+                out,
+                "    label={} labeljust=l;",
+                quote(&(self.notes.join("\n") + "\n"))
+            );
+        }
+
+        for node in &self.nodes {
+            let mut attributes = vec![];
+            if let Some(fill) = node.fill {
+                attributes.push(format!("style=filled fillcolor=\"{}\"", fill.hex()));
+            }
+            if let Some(outline) = node.outline {
+                attributes.push(format!("color=\"{}\" penwidth=2", outline.hex()));
+            }
+            let _ = writeln!(out, "    {}{};", quote(&node.id), list(&attributes));
+        }
+
+        for edge in &self.edges {
+            let mut attributes = vec![];
+            if !edge.label.is_empty() {
+                attributes.push(format!("label={}", quote(&edge.label)));
+            }
+            if let Some(color) = edge.color {
+                attributes.push(format!("color=\"{}\" penwidth=2", color.hex()));
+            }
+            let _ = writeln!(
+                out,
+                "    {} -> {}{};",
+                quote(&self.nodes[edge.from].id),
+                quote(&self.nodes[edge.to].id),
+                list(&attributes)
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Quotes a DOT id, escaping quotes and turning line breaks into left-aligned ones.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\l");
+    format!("\"{escaped}\"")
+}
+
+fn list(attributes: &[String]) -> String {
+    if attributes.is_empty() {
+        String::new()
+    } else {
+        format!(" [{}]", attributes.join(" "))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Graph;
+    use crate::viz::Color;
+
+    #[test]
+    fn renders_adjacency() {
+        let mut graph = Graph::from_adjacency(
+            "08",
+            [
+                ("AAA", vec![("BBB", "L"), ("BBB", "R")]),
+                ("BBB", vec![("AAA", "L"), ("ZZZ", "R")]),
+            ],
+        );
+        graph.highlight("AAA", Color::Green);
+        graph.outline("BBB", Color::Blue);
+        graph.color_edge("BBB", "ZZZ", "R", Color::Blue);
+        graph.note("one \"goal\"");
+
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(
+            graph.render(),
+            "digraph \"08\" {\n    \
+                node [shape=circle fontname=monospace];\n    \
+                edge [fontname=monospace];\n    \
+                label=\"one \\\"goal\\\"\\l\" labeljust=l;\n    \
+                \"AAA\" [style=filled fillcolor=\"#00cc00\"];\n    \
+                \"BBB\" [color=\"#5588ff\" penwidth=2];\n    \
+                \"ZZZ\";\n    \
+                \"AAA\" -> \"BBB\" [label=\"L\"];\n    \
+                \"AAA\" -> \"BBB\" [label=\"R\"];\n    \
+                \"BBB\" -> \"AAA\" [label=\"L\"];\n    \
+                \"BBB\" -> \"ZZZ\" [label=\"R\" color=\"#5588ff\" penwidth=2];\n\
+            }\n"
+        );
+    }
+
+    #[test]
+    fn renders_plain_edges() {
+        let mut graph = Graph::new("g");
+        graph.edge(1, 2, "");
+        assert!(graph.render().contains("    \"1\" -> \"2\";\n"));
+    }
+}
@@ -0,0 +1,155 @@
+/// Renders the grids of frames as an animated GIF.
+///
+/// Cells are drawn as colored squares: `.` and spaces as background, other characters in a dim
+/// gray and highlighted cells in their color. Titles, labels and notes are left out, since drawing
+/// text would need a font. Frames without a grid are skipped.
+use std::borrow::Cow;
+use std::io;
+use std::time::Duration;
+
+use gif::{Encoder, Repeat};
+
+use super::{Color, Frame, Grid, BACKGROUND};
+
+/// The largest side of the image that cells are scaled up to.
+const MAX_SIZE: usize = 800;
+const MAX_CELL: usize = 12;
+
+const CELL: [u8; 3] = [0x44, 0x44, 0x55];
+const COLORS: [Color; 7] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+];
+
+/// Renders the grids of `frames` as a looping GIF that shows each frame for `delay`.
+///
+/// # Errors
+/// Returns an error if no frame has a grid or the grids don't fit into a GIF.
+pub fn render(frames: &[Frame], delay: Duration) -> io::Result<Vec<u8>> {
+    let grids: Vec<&Grid> = frames.iter().filter_map(|f| f.grid.as_ref()).collect();
+    let columns = grids.iter().map(|g| g.width()).max().unwrap_or(0);
+    let rows = grids.iter().map(|g| g.height()).max().unwrap_or(0);
+    if columns == 0 || rows == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there are no grids to draw.",
+        ));
+    }
+
+    let cell = (MAX_SIZE / columns.max(rows)).clamp(1, MAX_CELL);
+    let (width, height) = (columns * cell, rows * cell);
+    let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "the grids are too large.");
+    let (w, h) = (
+        u16::try_from(width).map_err(|_| too_large())?,
+        u16::try_from(height).map_err(|_| too_large())?,
+    );
+    // GIF delays are in hundredths of a second.
+    let delay = u16::try_from(delay.as_millis() / 10)
+        .unwrap_or(u16::MAX)
+        .max(1);
+
+    let mut out = vec![];
+    {
+        let mut encoder = Encoder::new(&mut out, w, h, &palette()).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for grid in grids {
+            let frame = gif::Frame {
+                width: w,
+                height: h,
+                delay,
+                buffer: Cow::Owned(pixels(grid, cell, width, height)),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+    }
+
+    Ok(out)
+}
+
+/// Background, cells and the highlight colors, padded to 16 entries.
+fn palette() -> Vec<u8> {
+    let mut palette: Vec<u8> = [BACKGROUND, CELL]
+        .into_iter()
+        .chain(COLORS.map(Color::rgb))
+        .flatten()
+        .collect();
+    palette.resize(16 * 3, 0);
+    palette
+}
+
+/// Palette indices of a `width` by `height` image of `grid`, with `cell` pixels per cell.
+fn pixels(grid: &Grid, cell: usize, width: usize, height: usize) -> Vec<u8> {
+    let mut pixels = vec![0; width * height];
+    // leave a gap between cells that are large enough for it.
+    let size = if cell >= 4 { cell - 1 } else { cell };
+
+    for (y, row) in grid.rows().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            let index = match grid.color(x, y) {
+                Some(color) => 2 + COLORS.iter().position(|&c| c == color).unwrap() as u8,
+                None if c == '.' || c == ' ' => continue,
+                None => 1,
+            };
+            for dy in 0..size {
+                let start = (y * cell + dy) * width + x * cell;
+                pixels[start..start + size].fill(index);
+            }
+        }
+    }
+
+    pixels
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{pixels, render};
+    use crate::viz::{Color, Frame, Grid};
+
+    #[test]
+    fn renders_gif() {
+        let mut grid = Grid::from_lines("#.\n.*");
+        grid.highlight(1, 1, Color::Yellow);
+        let frames = [
+            Frame::new("first").with_grid(grid.clone()),
+            Frame::new("text only"),
+            Frame::new("second").with_grid(grid),
+        ];
+        let gif = render(&frames, Duration::from_millis(100)).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        let mut delays = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (24, 24));
+            delays.push(frame.delay);
+        }
+        // frames without a grid are skipped.
+        assert_eq!(delays, vec![10, 10]);
+    }
+
+    #[test]
+    fn draws_cells() {
+        let mut grid = Grid::from_lines("#.\n.*");
+        grid.highlight(1, 1, Color::Yellow);
+        assert_eq!(pixels(&grid, 1, 2, 2), vec![1, 0, 0, 4]);
+    }
+
+    #[test]
+    fn fails_without_grids() {
+        assert!(render(&[Frame::new("text")], Duration::from_millis(100)).is_err());
+    }
+}
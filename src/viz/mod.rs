@@ -0,0 +1,392 @@
+/// Frames of puzzle state that solutions emit for visual debugging.
+///
+/// Solutions call [`emit`] with a closure that builds a [`Frame`]: a grid with highlighted cells,
+/// colored labels and notes. Frames are only built and recorded if the crate is compiled with the
+/// `viz` feature and the solution runs with `--viz` or `--export <path>`. Otherwise [`emit`] is an
+/// empty function and the closure is optimized away, so benchmarks are unaffected.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, io};
+
+pub mod asciicast;
+pub mod dot;
+pub mod gif;
+pub mod svg;
+pub mod terminal;
+
+/// The colors available to highlight cells and labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    /// The ANSI escape sequence that sets this color as foreground.
+    #[must_use]
+    pub fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::Gray => "\x1b[90m",
+        }
+    }
+
+    /// The color as RGB, matching common terminal themes on a dark background.
+    #[must_use]
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Red => [0xff, 0x55, 0x55],
+            Color::Green => [0x00, 0xcc, 0x00],
+            Color::Yellow => [0xff, 0xff, 0x66],
+            Color::Blue => [0x55, 0x88, 0xff],
+            Color::Magenta => [0xff, 0x55, 0xff],
+            Color::Cyan => [0x55, 0xff, 0xff],
+            Color::Gray => [0x66, 0x66, 0x66],
+        }
+    }
+
+    /// The color as a CSS hex color like `#ff5555`.
+    #[must_use]
+    pub fn hex(self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// Background and default text color of rendered frames.
+pub const BACKGROUND: [u8; 3] = [0x0f, 0x0f, 0x23];
+pub const FOREGROUND: [u8; 3] = [0xcc, 0xcc, 0xcc];
+
+/* -------------------------------------------------------------------------- */
+
+/// A grid of characters, some of which are highlighted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grid {
+    cells: Vec<Vec<char>>,
+    highlights: HashMap<(usize, usize), Color>,
+}
+
+impl Grid {
+    /// Creates a `width` x `height` grid filled with `fill`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, fill: char) -> Self {
+        Self {
+            cells: vec![vec![fill; width]; height],
+            highlights: HashMap::new(),
+        }
+    }
+
+    /// Creates a grid with one row per line of `text`.
+    #[must_use]
+    pub fn from_lines(text: &str) -> Self {
+        Self {
+            cells: text.lines().map(|line| line.chars().collect()).collect(),
+            highlights: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.cells.get(y)?.get(x).copied()
+    }
+
+    /// Sets the character at `(x, y)`, if that cell exists.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = c;
+        }
+    }
+
+    /// Highlights the cell at `(x, y)`. A later highlight of the same cell replaces this one.
+    pub fn highlight(&mut self, x: usize, y: usize, color: Color) {
+        self.highlights.insert((x, y), color);
+    }
+
+    /// The highlight color of the cell at `(x, y)`, if any.
+    #[must_use]
+    pub fn color(&self, x: usize, y: usize) -> Option<Color> {
+        self.highlights.get(&(x, y)).copied()
+    }
+
+    /// Iterates over the rows of the grid.
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.cells.iter().map(Vec::as_slice)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A colored piece of text shown below the grid, e.g. a legend entry or a running total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub text: String,
+    pub color: Color,
+}
+
+/// One step of a visualization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    pub grid: Option<Grid>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_grid(mut self, grid: Grid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, text: impl Into<String>, color: Color) -> Self {
+        self.labels.push(Label {
+            text: text.into(),
+            color,
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn note(mut self, text: impl Into<String>) -> Self {
+        self.notes.push(text.into());
+        self
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "viz")]
+mod recorder {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    use super::Frame;
+
+    static RECORDING: AtomicBool = AtomicBool::new(false);
+    static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+    static ENABLED: AtomicBool = AtomicBool::new(false);
+
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    pub fn emit(frame: impl FnOnce() -> Frame) {
+        if RECORDING.load(Ordering::Relaxed) {
+            let frame = frame();
+            FRAMES.lock().unwrap().push(frame);
+        }
+    }
+
+    pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+        if !enabled() {
+            return (f(), vec![]);
+        }
+        FRAMES.lock().unwrap().clear();
+        RECORDING.store(true, Ordering::Relaxed);
+        let res = f();
+        RECORDING.store(false, Ordering::Relaxed);
+        (res, std::mem::take(&mut *FRAMES.lock().unwrap()))
+    }
+}
+
+/// Whether visualizations are turned on, i.e. the `viz` feature is enabled and `--viz` or
+/// `--export` was passed. Solutions that build several frames at once can check this to skip that
+/// work, it is a constant `false` without the feature.
+#[must_use]
+pub fn enabled() -> bool {
+    #[cfg(feature = "viz")]
+    return recorder::enabled();
+    #[cfg(not(feature = "viz"))]
+    return false;
+}
+
+/// Records the frame built by `frame` while a part runs with visualizations turned on.
+#[cfg(feature = "viz")]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    recorder::emit(frame);
+}
+
+/// Records the frame built by `frame` while a part runs with visualizations turned on.
+#[cfg(not(feature = "viz"))]
+#[inline(always)]
+pub fn emit(_frame: impl FnOnce() -> Frame) {}
+
+/// Turns visualizations on or off for the parts that run after, as `--viz` and `--export` do.
+pub fn set_enabled(enabled: bool) {
+    #[cfg(feature = "viz")]
+    recorder::set_enabled(enabled);
+    #[cfg(not(feature = "viz"))]
+    let _ = enabled;
+}
+
+/// Runs `f` and returns the frames it emitted. Returns no frames if visualizations are off.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    #[cfg(feature = "viz")]
+    return recorder::record(f);
+    #[cfg(not(feature = "viz"))]
+    return (f(), vec![]);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The delay between frames: `AOC_VIZ_DELAY_MS`, or 100ms if that is not set.
+#[must_use]
+pub fn frame_delay() -> Duration {
+    env::var("AOC_VIZ_DELAY_MS")
+        .ok()
+        .and_then(|ms| ms.parse().ok())
+        .map_or(Duration::from_millis(100), Duration::from_millis)
+}
+
+/// Writes `frames` to `path` as an animated SVG, asciicast or GIF, depending on its extension
+/// (`.svg`, `.cast` or `.gif`).
+pub fn export(frames: &[Frame], path: &Path) -> io::Result<()> {
+    let delay = frame_delay();
+    let contents = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("svg") => svg::render(frames, delay).into_bytes(),
+        Some("cast") => asciicast::render(frames, delay).into_bytes(),
+        Some("gif") => gif::render(frames, delay)?,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot export to \"{}\", expected a .svg, .cast or .gif file.",
+                    path.display()
+                ),
+            ))
+        }
+    };
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Plays back the frames a part emitted if `play` is set (`--viz`), and exports them to `export`
+/// (`--export <path>`). Exports of part 2 get a `-2` suffix, e.g. `out/03-2.svg`.
+pub fn present(frames: &[Frame], part: u8, play: bool, export: Option<&Path>) {
+    if frames.is_empty() {
+        return;
+    }
+
+    if play {
+        terminal::play(frames);
+    }
+
+    let Some(path) = export else {
+        return;
+    };
+    let path = part_path(path, part);
+    match self::export(frames, &path) {
+        Ok(()) => println!("Exported {} frames to \"{}\"", frames.len(), path.display()),
+        Err(e) => eprintln!("Failed to export frames: {e}"),
+    }
+}
+
+/// Inserts the part number before the extension: `out/03.svg` becomes `out/03-1.svg`.
+fn part_path(path: &Path, part: u8) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{part}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{part}"),
+    };
+    path.with_file_name(name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{part_path, record, Color, Frame, Grid};
+
+    #[test]
+    fn builds_grids() {
+        let mut grid = Grid::from_lines("467..\n...*.\n");
+        assert_eq!((grid.width(), grid.height()), (5, 2));
+        assert_eq!(grid.get(3, 1), Some('*'));
+        assert_eq!(grid.get(5, 0), None);
+
+        grid.set(0, 0, '#');
+        grid.set(10, 10, '#');
+        grid.highlight(3, 1, Color::Yellow);
+        grid.highlight(3, 1, Color::Red);
+        assert_eq!(grid.get(0, 0), Some('#'));
+        assert_eq!(grid.color(3, 1), Some(Color::Red));
+        assert_eq!(grid.color(0, 0), None);
+        assert_eq!(
+            Grid::new(3, 2, '.').rows().collect::<Vec<_>>(),
+            [['.'; 3]; 2]
+        );
+    }
+
+    #[test]
+    fn builds_frames() {
+        let frame = Frame::new("step 1")
+            .with_grid(Grid::new(1, 1, '.'))
+            .label("sum: 4", Color::Green)
+            .note("a note");
+        assert_eq!(frame.title, "step 1");
+        assert!(frame.grid.is_some());
+        assert_eq!(frame.labels[0].color, Color::Green);
+        assert_eq!(frame.notes, ["a note"]);
+    }
+
+    #[test]
+    fn records_nothing_without_viz_flag() {
+        let (res, frames) = record(|| {
+            super::emit(|| unreachable!("frames are not built without `--viz`"));
+            42
+        });
+        assert_eq!(res, 42);
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn inserts_part_into_export_path() {
+        assert_eq!(
+            part_path(Path::new("out/03.svg"), 1),
+            Path::new("out/03-1.svg")
+        );
+        assert_eq!(part_path(Path::new("anim"), 2), Path::new("anim-2"));
+        assert_eq!(Color::Red.hex(), "#ff5555");
+    }
+}
@@ -0,0 +1,171 @@
+/// Renders frames as a self-contained animated SVG.
+///
+/// Every frame is a group of monospace text that a CSS animation shows for one frame delay per
+/// cycle, so the file plays in any browser and loops forever without scripts.
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use super::{Color, Frame, BACKGROUND, FOREGROUND};
+
+const FONT_SIZE: f64 = 14.0;
+const CHAR_WIDTH: f64 = FONT_SIZE * 0.6;
+const LINE_HEIGHT: f64 = FONT_SIZE * 1.25;
+const PADDING: f64 = 10.0;
+
+/// A line of text in runs of the same color.
+type Line = Vec<(Option<Color>, String)>;
+
+/// Renders `frames` as an animated SVG that shows each frame for `delay`.
+#[must_use]
+pub fn render(frames: &[Frame], delay: Duration) -> String {
+    let frames: Vec<Vec<Line>> = frames.iter().map(lines).collect();
+    let columns = frames
+        .iter()
+        .flatten()
+        .map(|line| line.iter().map(|(_, text)| text.chars().count()).sum())
+        .max()
+        .unwrap_or(0);
+    let rows = frames.iter().map(Vec::len).max().unwrap_or(0);
+
+    let width = columns as f64 * CHAR_WIDTH + 2.0 * PADDING;
+    let height = rows as f64 * LINE_HEIGHT + 2.0 * PADDING;
+    let [r, g, b] = BACKGROUND;
+    let [fr, fg, fb] = FOREGROUND;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"##
+    );
+    let _ = writeln!(
+        out,
+        "<style>text{{font-family:monospace;font-size:{FONT_SIZE}px;white-space:pre;fill:#{fr:02x}{fg:02x}{fb:02x}}}"
+    );
+    if frames.len() > 1 {
+        let cycle = delay.as_millis() * frames.len() as u128;
+        let shown = 100.0 / frames.len() as f64;
+        let _ = writeln!(
+            out,
+            ".f{{visibility:hidden;animation:show {cycle}ms step-end infinite}}\n\
+             @keyframes show{{0%{{visibility:visible}}{shown:.4}%{{visibility:hidden}}}}"
+        );
+    }
+    let _ = writeln!(out, "</style>");
+    let _ = writeln!(
+        out,
+        r##"<rect width="100%" height="100%" fill="#{r:02x}{g:02x}{b:02x}"/>"##
+    );
+
+    for (i, frame) in frames.iter().enumerate() {
+        if frames.len() > 1 {
+            let start = delay.as_millis() * i as u128;
+            let _ = writeln!(out, r#"<g class="f" style="animation-delay:{start}ms">"#);
+        } else {
+            let _ = writeln!(out, "<g>");
+        }
+        for (y, line) in frame.iter().enumerate() {
+            let top = PADDING + (y as f64 + 0.8) * LINE_HEIGHT;
+            let _ = write!(out, r#"<text x="{PADDING}" y="{top}">"#);
+            for (color, text) in line {
+                match color {
+                    Some(color) => {
+                        let _ = write!(
+                            out,
+                            r#"<tspan fill="{}" font-weight="bold">{}</tspan>"#,
+                            color.hex(),
+                            escape(text)
+                        );
+                    }
+                    None => out.push_str(&escape(text)),
+                }
+            }
+            let _ = writeln!(out, "</text>");
+        }
+        let _ = writeln!(out, "</g>");
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// The lines of a frame, laid out like [`super::terminal::render`].
+fn lines(frame: &Frame) -> Vec<Line> {
+    let mut lines = vec![];
+
+    if !frame.title.is_empty() {
+        lines.push(vec![(None, frame.title.clone())]);
+    }
+
+    if let Some(grid) = &frame.grid {
+        for (y, row) in grid.rows().enumerate() {
+            let mut line: Line = vec![];
+            for (x, &c) in row.iter().enumerate() {
+                let color = grid.color(x, y);
+                match line.last_mut() {
+                    Some((last, text)) if *last == color => text.push(c),
+                    _ => line.push((color, c.to_string())),
+                }
+            }
+            lines.push(line);
+        }
+    }
+
+    if !frame.labels.is_empty() {
+        let mut line: Line = vec![];
+        for (i, label) in frame.labels.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "  " };
+            line.push((None, separator.to_string()));
+            line.push((Some(label.color), format!("● {}", label.text)));
+        }
+        lines.push(line);
+    }
+
+    for note in &frame.notes {
+        lines.push(vec![(None, note.clone())]);
+    }
+
+    lines
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::render;
+    use crate::viz::{Color, Frame, Grid};
+
+    #[test]
+    fn renders_animated_frames() {
+        let mut grid = Grid::from_lines("1<\n.*");
+        grid.highlight(1, 1, Color::Yellow);
+        let frames = [
+            Frame::new("first").with_grid(grid),
+            Frame::new("second").note("a & b"),
+        ];
+        let svg = render(&frames, Duration::from_millis(50));
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("animation:show 100ms step-end infinite"));
+        assert!(svg.contains("50.0000%{visibility:hidden}"));
+        assert!(svg.contains(r#"<g class="f" style="animation-delay:50ms">"#));
+        assert!(svg.contains(r##"1&lt;</text>"##));
+        assert!(svg.contains(r##".<tspan fill="#ffff66" font-weight="bold">*</tspan>"##));
+        assert!(svg.contains("a &amp; b"));
+    }
+
+    #[test]
+    fn renders_single_frame_without_animation() {
+        let svg = render(&[Frame::new("only")], Duration::from_millis(50));
+        assert!(!svg.contains("animation"));
+        assert!(svg.contains(">only</text>"));
+    }
+}
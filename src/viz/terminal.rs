@@ -0,0 +1,206 @@
+/// Renders frames with ANSI escape codes and plays them back in the terminal.
+///
+/// Playback controls: `space` pauses and resumes, `n` and `p` step to the next and previous
+/// frame, `+` and `-` change the speed and `q` stops. Keys are read without waiting for enter on
+/// terminals that support `stty`. If stdout is not a terminal, frames are printed one after another.
+use std::fmt::Write as _;
+use std::io::{stdin, stdout, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use super::Frame;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub(super) const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Renders a frame as text with ANSI colors.
+#[must_use]
+pub fn render(frame: &Frame) -> String {
+    let mut out = String::new();
+
+    if !frame.title.is_empty() {
+        let _ = writeln!(out, "{ANSI_BOLD}{}{ANSI_RESET}", frame.title);
+    }
+
+    if let Some(grid) = &frame.grid {
+        for (y, row) in grid.rows().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                match grid.color(x, y) {
+                    Some(color) => {
+                        let _ = write!(out, "{}{ANSI_BOLD}{c}{ANSI_RESET}", color.ansi());
+                    }
+                    None => out.push(c),
+                }
+            }
+            out.push('\n');
+        }
+    }
+
+    if !frame.labels.is_empty() {
+        let labels: Vec<String> = frame
+            .labels
+            .iter()
+            .map(|label| format!("{}● {}{ANSI_RESET}", label.color.ansi(), label.text))
+            .collect();
+        let _ = writeln!(out, "{}", labels.join("  "));
+    }
+
+    for note in &frame.notes {
+        let _ = writeln!(out, "{ANSI_ITALIC}{note}{ANSI_RESET}");
+    }
+
+    out
+}
+
+/// Plays `frames` back in the terminal, see [`super::frame_delay`] for the speed.
+/// Does nothing if there are no frames.
+pub fn play(frames: &[Frame]) {
+    if frames.is_empty() {
+        return;
+    }
+
+    let mut stdout = stdout();
+    if !stdout.is_terminal() {
+        for frame in frames {
+            let _ = writeln!(stdout, "{}", render(frame));
+        }
+        return;
+    }
+
+    let mut delay = super::frame_delay();
+    let _raw = RawMode::enable();
+    let keys = keys().lock().unwrap();
+    // drop keys that were pressed before playback started.
+    while keys.try_recv().is_ok() {}
+
+    let mut i = 0;
+    let mut paused = false;
+    loop {
+        let status = format!(
+            "{ANSI_ITALIC}frame {}/{}{} · space: pause · n/p: step · +/-: speed · q: quit{ANSI_RESET}",
+            i + 1,
+            frames.len(),
+            if paused { " (paused)" } else { "" }
+        );
+        let _ = write!(stdout, "{CLEAR_SCREEN}{}\n{status}\n", render(&frames[i]));
+        let _ = stdout.flush();
+
+        let key = if paused {
+            keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            keys.recv_timeout(delay)
+        };
+
+        match key {
+            Ok(b' ') => paused = !paused,
+            Ok(b'n') => {
+                paused = true;
+                i = (i + 1).min(frames.len() - 1);
+            }
+            Ok(b'p') => {
+                paused = true;
+                i = i.saturating_sub(1);
+            }
+            Ok(b'+') => delay = (delay / 2).max(Duration::from_millis(1)),
+            Ok(b'-') => delay = (delay * 2).min(Duration::from_secs(5)),
+            Ok(b'q') => break,
+            Ok(_) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                if i + 1 == frames.len() {
+                    break;
+                }
+                i += 1;
+            }
+            // without stdin there are no controls, so play through to the end.
+            Err(RecvTimeoutError::Disconnected) => {
+                if i + 1 == frames.len() {
+                    break;
+                }
+                paused = false;
+                thread::sleep(delay);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Key presses from stdin, read by a single background thread for the lifetime of the process.
+fn keys() -> &'static Mutex<Receiver<u8>> {
+    static KEYS: OnceLock<Mutex<Receiver<u8>>> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for byte in stdin().lock().bytes() {
+                let Ok(byte) = byte else { break };
+                if tx.send(byte).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(rx)
+    })
+}
+
+/// Turns off line buffering and echo of the terminal while it is alive.
+struct RawMode {
+    enabled: bool,
+}
+
+impl RawMode {
+    fn enable() -> Self {
+        Self {
+            enabled: stty(&["-icanon", "-echo"]),
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if self.enabled {
+            stty(&["icanon", "echo"]);
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> bool {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::viz::{Color, Frame, Grid};
+
+    #[test]
+    fn renders_highlights_labels_and_notes() {
+        let mut grid = Grid::from_lines("1.\n.*");
+        grid.highlight(1, 1, Color::Yellow);
+        let frame = Frame::new("gears")
+            .with_grid(grid)
+            .label("gear", Color::Yellow)
+            .note("one gear");
+
+        assert_eq!(
+            render(&frame),
+            "\x1b[1mgears\x1b[0m\n\
+             1.\n\
+             .\x1b[33m\x1b[1m*\x1b[0m\n\
+             \x1b[33m● gear\x1b[0m\n\
+             \x1b[3mone gear\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn renders_empty_frame() {
+        assert_eq!(render(&Frame::default()), "");
+    }
+}